@@ -33,6 +33,10 @@ pub trait LlmAdapter: Send + Sync {
     // Back-compat convenience (defaults to Flash)
     async fn generate_json(&self, prompt: &str, schema: Option<JsonValue>, temperature: Option<f32>) -> Result<JsonValue>;
     async fn generate_text(&self, prompt: &str, system: Option<&str>, temperature: Option<f32>) -> Result<String>;
+    // generate_text plus the provider that produced the text (a cascade reports the inner adapter that answered)
+    async fn generate_text_from(&self, prompt: &str, system: Option<&str>, temperature: Option<f32>) -> Result<(String, &'static str)> {
+        Ok((self.generate_text(prompt, system, temperature).await?, self.provider_id()))
+    }
 
     // New: explicit model selection (e.g., "gemini-2.5-pro" or "gemini-2.5-flash")
     async fn generate_json_model(&self, model: &str, prompt: &str, schema: Option<JsonValue>, temperature: Option<f32>) -> Result<JsonValue>;
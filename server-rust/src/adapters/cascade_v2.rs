@@ -43,6 +43,17 @@ impl LlmAdapter for CascadeAdapter {
         Err(last_err.unwrap_or_else(|| anyhow::anyhow!("all adapters failed")))
     }
 
+    async fn generate_text_from(&self, prompt: &str, system: Option<&str>, temperature: Option<f32>) -> Result<(String, &'static str)> {
+        let mut last_err: Option<anyhow::Error> = None;
+        for a in &self.inner {
+            match a.generate_text_from(prompt, system, temperature).await {
+                Ok(v) => return Ok(v),
+                Err(e) => { last_err = Some(e); }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("all adapters failed")))
+    }
+
     async fn generate_json_model(&self, model: &str, prompt: &str, schema: Option<JsonValue>, temperature: Option<f32>) -> Result<JsonValue> {
         let (target_prov, model_name_opt) = parse_provider_pref(model);
         let selected: Vec<&Box<super::AdapterDyn>> = if let Some(tp) = target_prov {
@@ -147,3 +147,18 @@ pub fn user_interjection_schema() -> serde_json::Value {
     })
 }
 
+
+// Targeted rewrites of individual guide fields (keyed by JSON pointer path)
+pub fn field_fix_schema() -> serde_json::Value {
+    json!({
+      "type": "object",
+      "properties": {
+        "fixes": {"type": "array", "items": {
+          "type": "object", "properties": {
+            "path": {"type": "string"},
+            "text": {"type": "string"}
+          }
+        }}
+      }
+    })
+}
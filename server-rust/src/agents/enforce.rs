@@ -0,0 +1,155 @@
+use serde_json::{json, Value};
+
+use crate::adapters::AdapterDyn;
+use super::orchestrator::{MODEL_FLASH, OAI_4O_MINI};
+
+const DEFAULT_ROUNDS: usize = 2;
+
+// A text field of the guide that still contains banned terms
+#[derive(Debug, Clone)]
+pub struct FieldHit { pub path: String, pub text: String, pub terms: Vec<String> }
+
+impl FieldHit {
+    fn to_json(&self) -> Value { json!({"path": self.path, "text": self.text, "terms": self.terms}) }
+}
+
+// Text fields we police. tone.dosAndDonts.donts is skipped on purpose: it may quote buzzwords to forbid them.
+fn text_paths(core: &Value) -> Vec<String> {
+    let mut paths: Vec<String> = ["/mission", "/audience", "/elevatorPitch", "/tone/description"].iter().map(|s| s.to_string()).collect();
+    let len = |p: &str| core.pointer(p).and_then(|v| v.as_array()).map(|a| a.len()).unwrap_or(0);
+    for i in 0..len("/tone/traits") { paths.push(format!("/tone/traits/{}", i)); }
+    for i in 0..len("/tone/dosAndDonts/dos") { paths.push(format!("/tone/dosAndDonts/dos/{}", i)); }
+    for i in 0..len("/taglines") {
+        paths.push(format!("/taglines/{}/tagline", i));
+        paths.push(format!("/taglines/{}/rationale", i));
+    }
+    paths
+}
+
+/// Fields whose text contains a banned term for the guide's language `lang`.
+pub fn scan_banned(core: &Value, lang: &str, extra: &[String]) -> Vec<FieldHit> {
+    let mut hits = Vec::new();
+    for path in text_paths(core) {
+        let Some(text) = core.pointer(&path).and_then(|v| v.as_str()) else { continue };
+        let terms = crate::text::lint::find_banned(text, lang, extra);
+        if !terms.is_empty() { hits.push(FieldHit { path, text: text.to_string(), terms }); }
+    }
+    hits
+}
+
+/// Re-prompts only the fields that still contain banned terms, up to BAN_ENFORCE_ROUNDS rounds.
+/// Returns the fields left unresolved (empty when the guide is clean); a failed fix call ends the rounds early.
+pub async fn enforce_banned_terms(
+    adapter: &AdapterDyn,
+    core: &mut Value,
+    lang: &str,
+    extra: &[String],
+    events: Option<&tokio::sync::mpsc::UnboundedSender<String>>,
+) -> Vec<FieldHit> {
+    let rounds: usize = std::env::var("BAN_ENFORCE_ROUNDS").ok().and_then(|s| s.parse().ok()).unwrap_or(DEFAULT_ROUNDS);
+    let mut hits = scan_banned(core, lang, extra);
+    for round in 1..=rounds {
+        if hits.is_empty() { break; }
+        tracing::info!(target: "orchestrator", "[ENFORCE] round {} — {} field(s) with banned terms", round, hits.len());
+        if let Some(tx) = events { let _ = tx.send(json!({"type":"enforce","kind":"round","data": {"round": round, "hits": hits.iter().map(|h| h.to_json()).collect::<Vec<_>>()}}).to_string()); }
+
+        let prompt = build_fix_prompt(&hits, lang, extra);
+        let schema = crate::adapters::schemas::field_fix_schema();
+        let out = match super::orchestrator::generate_with_retry(adapter, OAI_4O_MINI, MODEL_FLASH, &prompt, Some(schema), Some(0.4), events, "ORCH").await {
+            Ok(v) => v,
+            Err(e) => { tracing::warn!("[ENFORCE] fix generation failed: {}", e); break; }
+        };
+
+        let changed = apply_fixes(core, &hits, &out);
+        if let Some(tx) = events { let _ = tx.send(json!({"type":"enforce","kind":"changed","data": {"round": round, "changes": changed}}).to_string()); }
+        hits = scan_banned(core, lang, extra);
+    }
+    if !hits.is_empty() {
+        tracing::warn!(target: "orchestrator", "[ENFORCE] {} field(s) still contain banned terms", hits.len());
+        if let Some(tx) = events { let _ = tx.send(json!({"type":"enforce","kind":"unresolved","data": hits.iter().map(|h| h.to_json()).collect::<Vec<_>>()}).to_string()); }
+    }
+    hits
+}
+
+// Writes the model's fixes into the guide; returns the changes made
+fn apply_fixes(core: &mut Value, hits: &[FieldHit], out: &Value) -> Vec<Value> {
+    let mut changed = Vec::new();
+    for fix in out.get("fixes").and_then(|v| v.as_array()).cloned().unwrap_or_default() {
+        let (Some(path), Some(text)) = (fix.get("path").and_then(|v| v.as_str()), fix.get("text").and_then(|v| v.as_str())) else { continue };
+        // Only accept edits to fields we asked about
+        let Some(hit) = hits.iter().find(|h| h.path == path) else { continue };
+        if text.trim().is_empty() { continue; }
+        if let Some(slot) = core.pointer_mut(path) {
+            *slot = json!(text.trim());
+            changed.push(json!({"path": path, "before": hit.text, "after": text.trim(), "terms": hit.terms}));
+        }
+    }
+    changed
+}
+
+fn build_fix_prompt(hits: &[FieldHit], lang: &str, extra: &[String]) -> String {
+    let mut fields = String::new();
+    for h in hits { fields.push_str(&format!("- path: {}\n  text: {}\n  banned terms found: {}\n", h.path, h.text, h.terms.join(", "))); }
+    format!(
+        r#"Chief Copywriter (Flash) — BANNED TERM FIXES
+These brand guide fields contain banned buzzwords. Rewrite each one so it no longer uses any banned term (or a variant of it).
+Keep the meaning, length, language and voice. Use plain, specific words. Don't touch anything else.
+Banned terms: {ban}
+
+Fields:
+{fields}
+Return STRICT JSON: {{ "fixes": [{{ "path": string (copy exactly), "text": string }}] }}
+"#,
+        ban = crate::prompts::banned_terms(lang, extra).join(", "),
+        fields = fields
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_nested_banned_terms_and_applies_only_requested_fixes() {
+        let mut core = json!({
+            "mission": "Help teams ship faster.",
+            "tone": {"traits": ["Plain"], "dosAndDonts": {"dos": ["Be direct"], "donts": ["Never say synergy"]}},
+            "taglines": [{"tagline": "Ship it", "rationale": "Short and leverages our synergy with teams"}],
+        });
+        let hits = scan_banned(&core, "en", &["ship".to_string()]);
+        let paths: Vec<&str> = hits.iter().map(|h| h.path.as_str()).collect();
+        // donts may quote banned terms; the mission hits the guide's own ban
+        assert_eq!(paths, vec!["/mission", "/taglines/0/tagline", "/taglines/0/rationale"]);
+        let rationale = hits.iter().find(|h| h.path == "/taglines/0/rationale").unwrap();
+        assert!(rationale.terms.iter().any(|t| t == "synergy"));
+
+        let out = json!({"fixes": [
+            {"path": "/taglines/0/rationale", "text": "  Short, and built with the teams who use it "},
+            {"path": "/elevatorPitch", "text": "Not asked for"},
+            {"path": "/mission", "text": "   "},
+        ]});
+        let changed = apply_fixes(&mut core, &hits, &out);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(core["taglines"][0]["rationale"], "Short, and built with the teams who use it");
+        assert!(core.get("elevatorPitch").is_none());
+        assert_eq!(core["mission"], "Help teams ship faster.");
+        assert!(scan_banned(&core, "en", &[]).is_empty());
+    }
+
+    #[test]
+    fn french_guides_are_checked_against_the_french_list() {
+        let core = json!({
+            "mission": "Une synergie de pointe pour nos membres.",
+            "audience": "Des équipes qui veulent de la synergy, sans jargon.",
+            "elevatorPitch": "Un outil simple, fait au Québec.",
+        });
+        let hits = scan_banned(&core, "fr-CA", &["fait au Québec".to_string()]);
+        let found: Vec<(&str, Vec<String>)> = hits.iter().map(|h| (h.path.as_str(), h.terms.clone())).collect();
+        // The English list doesn't apply to French copy; the locale's own terms do
+        assert_eq!(found, vec![
+            ("/mission", vec!["synergie".to_string(), "de pointe".to_string()]),
+            ("/elevatorPitch", vec!["fait au Québec".to_string()]),
+        ]);
+        assert!(build_fix_prompt(&hits, "fr-CA", &[]).contains("synergie, levier"));
+    }
+}
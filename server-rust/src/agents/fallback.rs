@@ -0,0 +1,176 @@
+// Deterministic guide content used when the model calls fail. Everything is rendered from the actual
+// inputs; template sets are chosen per locale and vertical. To add one, append a TemplateSet to SETS.
+use serde_json::{json, Value};
+
+use crate::models::UserInputs;
+
+pub struct TemplateSet {
+    pub id: &'static str,
+    // Primary language subtag ("fr"); None matches any locale
+    pub locale: Option<&'static str>,
+    // Lowercase words matched against the words of the industry; empty = generic
+    pub verticals: &'static [&'static str],
+    // Placeholders: {brand} {industry} {audience} {in_region} {mission}
+    pub audience: &'static str,
+    pub default_audience: &'static str,
+    pub pitch_notes: &'static str,
+    pub mission: &'static str,
+    pub pitch: &'static str,
+    pub tone_description: &'static str,
+    pub dos: &'static [&'static str],
+    pub donts: &'static [&'static str],
+    pub taglines: &'static [(&'static str, &'static str)],
+    pub rationale: &'static str,
+}
+
+const EN_DOS: &[&str] = &["Use contractions (we're, it's)", "Prefer plain words over jargon", "Keep sentences short and concrete", "Talk about outcomes customers care about", "Sound confident but approachable", "Write like a person, not a brochure"];
+const EN_DONTS: &[&str] = &["Corporate or academic phrasing", "Buzzwords and vague claims", "Grand metaphors or clichés", "Overlong sentences", "Exclamation marks", "Empty filler"];
+
+pub const SETS: &[TemplateSet] = &[
+    TemplateSet {
+        id: "en-software",
+        locale: Some("en"),
+        verticals: &["software", "saas", "app", "web", "it", "technology", "automation", "ai"],
+        audience: "{brand} works with {audience}{in_region} who need {industry} that fits how their team already works. Triggers are manual work and tools that don't talk to each other; objections are budget, complexity and adoption.",
+        default_audience: "teams",
+        pitch_notes: "Lead with the workflow problem, then the fix. Address budget, complexity and adoption directly, and back claims with a short example.",
+        mission: "We build {industry} that helps {audience} get more done with less friction.",
+        pitch: "At {brand}, we build {industry} for {audience}{in_region}. We start from how your team works today, keep the scope clear, and ship in small steps so you see results early.",
+        tone_description: "{brand} speaks in a clear, conversational voice: friendly, direct, and human. Short sentences. Plain language. If an analogy helps, keep it simple and practical.",
+        dos: EN_DOS,
+        donts: EN_DONTS,
+        taglines: &[("Built For Real Work", "Plain promise: useful tools for day-to-day operations."), ("Make It Work Better", "Says what clients want: smoother, faster, fewer headaches."), ("Simple. Solid. Shipped.", "Conversational cadence with focus on delivery.")],
+        rationale: "Explains how '{tagline}' reflects outcomes and tone.",
+    },
+    TemplateSet {
+        id: "en",
+        locale: Some("en"),
+        verticals: &[],
+        audience: "{brand} serves {audience}{in_region} looking for dependable {industry}. They want clear answers, fair pricing and follow-through; common objections are cost, effort and trust.",
+        default_audience: "customers",
+        pitch_notes: "Focus on who/need/objections: name the trigger that brings people in, answer cost and effort objections, and show proof (reviews, results).",
+        mission: "We make {industry} simple and dependable for {audience}.",
+        pitch: "At {brand}, we provide {industry} for {audience}{in_region}. We keep things clear, do what we say, and focus on results you can see.",
+        tone_description: "{brand} speaks in a clear, conversational voice: friendly, direct, and human. Short sentences. Plain language. If an analogy helps, keep it simple and practical.",
+        dos: EN_DOS,
+        donts: EN_DONTS,
+        taglines: &[("Built For Real Life", "Plain promise that the work fits everyday needs."), ("Clear From The Start", "Signals honesty and no surprises."), ("Done Right, Done Simply", "Pairs quality with ease in a conversational cadence.")],
+        rationale: "Explains how '{tagline}' reflects outcomes and tone.",
+    },
+    TemplateSet {
+        id: "fr",
+        locale: Some("fr"),
+        verticals: &[],
+        audience: "{brand} s'adresse à {audience}{in_region} qui recherchent des services fiables en {industry}. Ils veulent des réponses claires, des prix justes et un vrai suivi; leurs objections portent sur le coût, l'effort et la confiance.",
+        default_audience: "clients",
+        pitch_notes: "Parler de qui, de quel besoin et de quelles objections : nommer le déclencheur, répondre aux objections de coût et d'effort, et montrer des preuves (avis, résultats).",
+        mission: "Nous rendons {industry} simple et fiable pour {audience}.",
+        pitch: "Chez {brand}, nous offrons {industry} à {audience}{in_region}. Nous restons clairs, nous tenons parole et nous visons des résultats concrets.",
+        tone_description: "{brand} parle d'une voix claire et conviviale : directe, humaine, sans jargon. Des phrases courtes. Des mots simples.",
+        dos: &["Vouvoyer le lecteur", "Préférer les mots simples au jargon", "Faire des phrases courtes et concrètes", "Parler des résultats qui comptent pour le client", "Rester confiant et accessible", "Écrire comme une personne, pas comme une brochure"],
+        donts: &["Tournures corporatives ou académiques", "Mots à la mode et promesses vagues", "Grandes métaphores ou clichés", "Phrases trop longues", "Points d'exclamation", "Remplissage creux"],
+        taglines: &[("Pensé pour le quotidien", "Promesse simple : un service qui s'adapte à la vraie vie."), ("Clair dès le départ", "Annonce l'honnêteté et l'absence de surprises."), ("Bien fait, tout simplement", "Associe qualité et simplicité sur un ton conversationnel.")],
+        rationale: "Explique comment « {tagline} » reflète les résultats et le ton.",
+    },
+];
+
+/// Values substituted into a template set.
+pub struct Ctx { pub brand: String, pub industry: String, pub audience: String, pub region: String, pub mission: String, pub locale: String }
+
+impl Ctx {
+    // Shared split context first (it is what the agents saw), falling back to the raw inputs
+    pub fn new(shared: &Value, inputs: &UserInputs) -> Ctx {
+        let pick = |key: &str, fallback: &str| shared.get(key).and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()).unwrap_or(fallback.trim()).to_string();
+        Ctx {
+            brand: pick("brandName", &inputs.brandName),
+            industry: pick("industry", &inputs.industry),
+            audience: pick("audience", &inputs.audience),
+            region: inputs.region.clone().unwrap_or_default().trim().to_string(),
+            mission: pick("mission", &inputs.mission),
+            locale: inputs.locale.clone().unwrap_or_else(|| "en".to_string()),
+        }
+    }
+}
+
+/// Best set for the locale and industry: locale + vertical, then locale generic, then English generic.
+pub fn select(locale: &str, industry: &str) -> &'static TemplateSet {
+    let lang = crate::text::lang::primary(locale);
+    let lower = industry.to_lowercase();
+    let words: Vec<&str> = lower.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    let locale_ok = |s: &&TemplateSet| s.locale.map(|l| l == lang).unwrap_or(true);
+    SETS.iter().filter(locale_ok).find(|s| s.verticals.iter().any(|v| words.contains(v)))
+        .or_else(|| SETS.iter().filter(locale_ok).find(|s| s.verticals.is_empty()))
+        .or_else(|| SETS.iter().find(|s| s.id == "en"))
+        .expect("English generic template set")
+}
+
+fn render(t: &str, set: &TemplateSet, ctx: &Ctx) -> String {
+    let audience = if ctx.audience.is_empty() { set.default_audience } else { ctx.audience.as_str() };
+    // Audience inputs are often full sentences; use the first clause as a noun phrase
+    let audience = audience.split(['.', ';', '\n']).next().unwrap_or(audience).trim().trim_end_matches(',');
+    let in_region = if ctx.region.is_empty() { String::new() } else if set.locale == Some("fr") { format!(" ({})", ctx.region) } else { format!(" in {}", ctx.region) };
+    let industry = if ctx.industry.is_empty() { "services" } else { ctx.industry.as_str() };
+    t.replace("{brand}", &ctx.brand)
+        .replace("{industry}", &lower_first(industry))
+        .replace("{audience}", &lower_first(audience))
+        .replace("{in_region}", &in_region)
+        .replace("{mission}", &ctx.mission)
+}
+
+fn lower_first(s: &str) -> String {
+    // Keep acronyms and proper nouns ("SMBs", "Toronto clinics") as written
+    let mut c = s.chars();
+    match (c.next(), c.next()) {
+        (Some(a), Some(b)) if a.is_uppercase() && b.is_lowercase() => format!("{}{}", a.to_lowercase(), &s[a.len_utf8()..]),
+        _ => s.to_string(),
+    }
+}
+
+pub fn set_for(ctx: &Ctx) -> &'static TemplateSet { select(&ctx.locale, &ctx.industry) }
+
+pub fn audience(ctx: &Ctx) -> String { let s = set_for(ctx); render(s.audience, s, ctx) }
+
+pub fn mission(ctx: &Ctx) -> String {
+    if !ctx.mission.is_empty() { return ctx.mission.clone(); }
+    let s = set_for(ctx);
+    render(s.mission, s, ctx)
+}
+
+pub fn pitch(ctx: &Ctx) -> String { let s = set_for(ctx); render(s.pitch, s, ctx) }
+
+pub fn pitch_notes(ctx: &Ctx) -> String { let s = set_for(ctx); render(s.pitch_notes, s, ctx) }
+
+pub fn tone(ctx: &Ctx, traits: Vec<Value>) -> Value {
+    let s = set_for(ctx);
+    json!({
+        "traits": traits,
+        "description": render(s.tone_description, s, ctx),
+        "dosAndDonts": { "dos": s.dos, "donts": s.donts },
+    })
+}
+
+pub fn taglines(ctx: &Ctx) -> Vec<Value> {
+    let s = set_for(ctx);
+    s.taglines.iter().map(|(t, r)| json!({"tagline": t, "rationale": r})).collect()
+}
+
+pub fn tagline_rationale(ctx: &Ctx, tagline: &str) -> String {
+    set_for(ctx).rationale.replace("{tagline}", tagline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_from_inputs_and_picks_sets() {
+        let ctx = Ctx { brand: "Harbor Dental".into(), industry: "Dental care".into(), audience: "Families with young kids".into(), region: "Ohio".into(), mission: String::new(), locale: "en-US".into() };
+        assert_eq!(set_for(&ctx).id, "en");
+        let a = audience(&ctx);
+        assert!(a.starts_with("Harbor Dental serves families with young kids in Ohio"));
+        assert!(!pitch(&ctx).contains("developer"));
+        assert_eq!(select("en", "IT services").id, "en-software");
+        assert_eq!(select("en", "Hair salon").id, "en");
+        assert_eq!(select("fr-CA", "Software").id, "fr");
+    }
+}
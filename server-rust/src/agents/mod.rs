@@ -1,5 +1,7 @@
 pub mod analysis;
 pub mod orchestrator;
 pub mod json;
+pub mod enforce;
+pub mod fallback;
 
 pub use json::batch_convert_notes;
@@ -2,11 +2,12 @@ use anyhow::Result;
 use serde_json::Value;
 
 use crate::{adapters::AdapterDyn, models::UserInputs};
+use super::fallback;
 
 const MODEL_PRO: &str = "gemini:gemini-2.5-pro";
-const MODEL_FLASH: &str = "gemini:gemini-2.5-flash";
+pub(crate) const MODEL_FLASH: &str = "gemini:gemini-2.5-flash";
 const OAI_4O: &str = "openai:gpt-4o";
-const OAI_4O_MINI: &str = "openai:gpt-4o-mini";
+pub(crate) const OAI_4O_MINI: &str = "openai:gpt-4o-mini";
 
 pub struct OrchestrationResult {
     pub guide_core: Value, // without palette/logo; palette merged in route
@@ -20,7 +21,9 @@ pub async fn generate_guide_multiagent(
     user_notes: Option<&std::sync::Arc<tokio::sync::Mutex<Vec<String>>>>,
 ) -> Result<OrchestrationResult> {
     // 1) Orchestrator (Pro): split inputs into briefs + shared context + initial checklist
-    let split_prompt = build_orchestrator_split_prompt(inputs);
+    let (lang, extra_banned) = crate::locales::generation_banned(inputs);
+    let ban = banlist(inputs, &lang, &extra_banned);
+    let split_prompt = build_orchestrator_split_prompt(inputs, &ban);
     let split_schema = crate::adapters::schemas::split_schema();
     let split = generate_with_retry(adapter, OAI_4O, OAI_4O_MINI, &split_prompt, Some(split_schema), Some(0.2), events, "ORCH").await?;
 
@@ -31,13 +34,15 @@ pub async fn generate_guide_multiagent(
     let bg_brief = split["bgBrief"].as_str().unwrap_or("");
     let me_brief = split["meBrief"].as_str().unwrap_or("");
     let cc_brief = split["ccBrief"].as_str().unwrap_or("");
+    // Template context for deterministic fallbacks
+    let fb = crate::agents::fallback::Ctx::new(shared, inputs);
 
     // 2) analysis roundtable — agents build shared understanding (logs only)
-    let analysis = crate::agents::analysis::run_analysis_round(adapter, shared, &banlist(), events, user_notes).await?;
+    let analysis = crate::agents::analysis::run_analysis_round(adapter, shared, &ban, events, user_notes).await?;
     tracing::info!(target: "orchestrator", "[ROUNDUP] analysis.cc_notes=\n{}", serde_json::to_string_pretty(&analysis.cc_notes).unwrap_or_default());
 
     // 3) BG (Flash): tone description + dos/donts
-    let mut bg_prompt = build_bg_prompt(shared, bg_brief, &checklist);
+    let mut bg_prompt = build_bg_prompt(shared, bg_brief, &checklist, &ban);
     if let Some(store) = user_notes { if let Some(snip) = snapshot_user_notes(store, 5).await { bg_prompt.push_str("\n\nLive chat (recent USER messages):\n"); bg_prompt.push_str(&snip); } }
     tracing::info!(target: "orchestrator", "[BG DELIVERABLE] prompt=\n{}", bg_prompt);
     if let Some(tx) = events { let _ = tx.send(format!("{}", serde_json::json!({"type":"deliverable","phase":"BG","kind":"prompt","data": bg_prompt }))); }
@@ -48,7 +53,7 @@ pub async fn generate_guide_multiagent(
         Err(e) => {
             tracing::warn!("[BG DELIVERABLE] generation failed: {} — using deterministic fallback", e);
             if let Some(tx) = events { let _ = tx.send(serde_json::json!({"type":"deliverable","phase":"BG","kind":"fallback","data":"BG failed; using deterministic tone"}).to_string()); }
-            fallback_bg_deliverable(shared)
+            fallback_bg_deliverable(shared, &fb)
         }
     };
     if let Some(tx) = events { let _ = tx.send(serde_json::json!({"type":"typing","role":"BG","state":"stop"}).to_string()); }
@@ -56,7 +61,7 @@ pub async fn generate_guide_multiagent(
     if let Some(tx) = events { let _ = tx.send(format!("{}", serde_json::json!({"type":"deliverable","phase":"BG","kind":"out","data": bg_out }))); }
 
     // 3) ME (Flash): audience + pitch scaffold/notes
-    let mut me_prompt = build_me_prompt(shared, me_brief, &checklist);
+    let mut me_prompt = build_me_prompt(shared, me_brief, &checklist, &ban);
     if let Some(store) = user_notes { if let Some(snip) = snapshot_user_notes(store, 5).await { me_prompt.push_str("\n\nLive chat (recent USER messages):\n"); me_prompt.push_str(&snip); } }
     tracing::info!(target: "orchestrator", "[ME DELIVERABLE] prompt=\n{}", me_prompt);
     if let Some(tx) = events { let _ = tx.send(format!("{}", serde_json::json!({"type":"deliverable","phase":"ME","kind":"prompt","data": me_prompt }))); }
@@ -67,7 +72,7 @@ pub async fn generate_guide_multiagent(
         Err(e) => {
             tracing::warn!("[ME DELIVERABLE] generation failed: {} — using deterministic fallback", e);
             if let Some(tx) = events { let _ = tx.send(serde_json::json!({"type":"deliverable","phase":"ME","kind":"fallback","data":"ME failed; using deterministic audience & pitchNotes"}).to_string()); }
-            fallback_me_deliverable(shared)
+            fallback_me_deliverable(&fb)
         }
     };
     if let Some(tx) = events { let _ = tx.send(serde_json::json!({"type":"typing","role":"ME","state":"stop"}).to_string()); }
@@ -75,7 +80,7 @@ pub async fn generate_guide_multiagent(
     if let Some(tx) = events { let _ = tx.send(format!("{}", serde_json::json!({"type":"deliverable","phase":"ME","kind":"out","data": me_out }))); }
 
     // 4) CC (Flash): mission + elevator pitch + taglines with rationale, given BG/ME outputs
-    let mut cc_prompt = build_cc_prompt(shared, cc_brief, &bg_out, &me_out, &checklist, inputs.existingTagline.as_deref());
+    let mut cc_prompt = build_cc_prompt(shared, cc_brief, &bg_out, &me_out, &checklist, inputs.existingTagline.as_deref(), &ban);
     if let Some(store) = user_notes { if let Some(snip) = snapshot_user_notes(store, 5).await { cc_prompt.push_str("\n\nLive chat (recent USER messages):\n"); cc_prompt.push_str(&snip); } }
     tracing::info!(target: "orchestrator", "[CC DELIVERABLE] prompt=\n{}", cc_prompt);
     if let Some(tx) = events { let _ = tx.send(format!("{}", serde_json::json!({"type":"deliverable","phase":"CC","kind":"prompt","data": cc_prompt }))); }
@@ -86,7 +91,7 @@ pub async fn generate_guide_multiagent(
         Err(e) => {
             tracing::warn!("[CC DELIVERABLE] generation failed: {} — using deterministic fallback", e);
             if let Some(tx) = events { let _ = tx.send(serde_json::json!({"type":"deliverable","phase":"CC","kind":"fallback","data":"CC failed; using deterministic mission, elevatorPitch & taglines"}).to_string()); }
-            fallback_cc_deliverable(shared)
+            fallback_cc_deliverable(&fb)
         }
     };
     if let Some(tx) = events { let _ = tx.send(serde_json::json!({"type":"typing","role":"CC","state":"stop"}).to_string()); }
@@ -94,7 +99,7 @@ pub async fn generate_guide_multiagent(
     if let Some(tx) = events { let _ = tx.send(format!("{}", serde_json::json!({"type":"deliverable","phase":"CC","kind":"out","data": cc_out }))); }
 
     // 5) Orchestrator (Pro): refine & assemble final JSON (no palette/logo)
-    let mut assemble_prompt = build_orchestrator_assemble_prompt(shared, &bg_out, &me_out, &cc_out);
+    let mut assemble_prompt = build_orchestrator_assemble_prompt(shared, &bg_out, &me_out, &cc_out, &ban);
     if let Some(store) = user_notes { if let Some(snip) = snapshot_user_notes(store, 5).await { assemble_prompt.push_str("\n\nLive chat (recent USER messages) to enforce in final output:\n"); assemble_prompt.push_str(&snip); } }
     tracing::info!(target: "orchestrator", "[ASSEMBLE] prompt=\n{}", assemble_prompt);
     if let Some(tx) = events { let _ = tx.send(format!("{}", serde_json::json!({"type":"assemble","kind":"prompt","data": assemble_prompt }))); let _ = tx.send(serde_json::json!({"type":"typing","role":"ORCH","state":"start"}).to_string()); }
@@ -112,13 +117,19 @@ pub async fn generate_guide_multiagent(
             Ok(repaired) => { final_core = repaired; }
             Err(e) => {
                 tracing::warn!("repair_guide_with_llm failed: {} — using deterministic fallback", e);
-                final_core = deterministic_fill(&final_core, shared, &bg_out, &me_out, &cc_out);
+                final_core = deterministic_fill(&final_core, shared, &bg_out, &me_out, &cc_out, &fb);
                 if let Some(tx) = events { let _ = tx.send(serde_json::json!({"type":"assemble","kind":"repair","data":"Applied deterministic fallback fill"}).to_string()); }
             }
         }
         if let Some(tx) = events { let _ = tx.send(format!("{}", serde_json::json!({"type":"assemble","kind":"out","data": final_core }))); }
     }
 
+    // 5c) Verify: no banned terms in any text field; targeted re-prompts for offenders
+    let unresolved = crate::agents::enforce::enforce_banned_terms(adapter, &mut final_core, &lang, &extra_banned, events).await;
+    if !unresolved.is_empty() {
+        tracing::warn!("banned terms remain in: {}", unresolved.iter().map(|h| h.path.as_str()).collect::<Vec<_>>().join(", "));
+    }
+
     // 6) Orchestrator updates checklist to done (internal)
     let mut final_checklist = checklist;
     if !final_checklist.contains("[x] delivery") {
@@ -178,7 +189,7 @@ Return STRICT JSON only (no wrappers).
     Ok(out)
 }
 
-fn deterministic_fill(current: &Value, shared: &Value, bg: &Value, me: &Value, cc: &Value) -> Value {
+fn deterministic_fill(current: &Value, shared: &Value, bg: &Value, me: &Value, cc: &Value, fb: &fallback::Ctx) -> Value {
     use serde_json::{json, Value as V};
     let mut out = current.clone();
     let o = out.as_object_mut().unwrap();
@@ -188,35 +199,29 @@ fn deterministic_fill(current: &Value, shared: &Value, bg: &Value, me: &Value, c
     // mission
     if o.get("mission").and_then(|v| v.as_str()).unwrap_or("").trim().is_empty() {
         if let Some(m) = cc.get("mission").and_then(|v| v.as_str()) { o.insert("mission".into(), json!(m)); }
-        else if let Some(m) = shared.get("mission").and_then(|v| v.as_str()) { o.insert("mission".into(), json!(m)); }
+        else { o.insert("mission".into(), json!(fallback::mission(fb))); }
     }
     // audience
     if o.get("audience").and_then(|v| v.as_str()).unwrap_or("").trim().is_empty() {
-        let brand = o.get("brandName").and_then(|v| v.as_str()).unwrap_or("");
-        let industry = o.get("industry").and_then(|v| v.as_str()).unwrap_or("");
         let base = me.get("audience").and_then(|v| v.as_str()).unwrap_or("");
-        let synth = if !base.is_empty() { base.to_string() } else { format!("{} serves Canadian organizations seeking dependable {} that address real operational constraints and objections like budget, complexity, and adoption.", brand, industry) };
+        let synth = if !base.is_empty() { base.to_string() } else { fallback::audience(fb) };
         o.insert("audience".into(), json!(synth));
     }
     // elevatorPitch
     if o.get("elevatorPitch").and_then(|v| v.as_str()).unwrap_or("").trim().is_empty() {
-        let brand = o.get("brandName").and_then(|v| v.as_str()).unwrap_or("");
         let mission = o.get("mission").and_then(|v| v.as_str()).unwrap_or("");
-        let aud = o.get("audience").and_then(|v| v.as_str()).unwrap_or("");
-        let mut pitch = format!("At {}, we design and ship custom web apps, automation, and AI that save time and move the needle. You work with one senior developer who keeps things clear, reduces risk, and fits the build to how your team works.", brand);
-        if !mission.is_empty() { pitch = format!("{} {}", pitch, mission); }
+        let mut pitch = cc.get("elevatorPitch").and_then(|v| v.as_str()).filter(|p| !p.trim().is_empty()).map(|p| p.to_string()).unwrap_or_else(|| fallback::pitch(fb));
+        if !mission.is_empty() && !pitch.contains(mission) { pitch = format!("{} {}", pitch, mission); }
         o.insert("elevatorPitch".into(), json!(pitch));
     }
     // tone
     if !o.contains_key("tone") || !o["tone"].is_object() {
-        let mut tone = serde_json::Map::new();
-        if let Some(t) = bg.get("tone").and_then(|v| v.as_object()) { tone = t.clone(); }
-        if !tone.contains_key("traits") { tone.insert("traits".into(), json!(shared.get("toneTraits").and_then(|v| v.as_array()).cloned().unwrap_or_default())); }
-        if !tone.contains_key("description") { tone.insert("description".into(), json!("Clear, conversational, and human. Short sentences. Plain language.")); }
-        if !tone.contains_key("dosAndDonts") { tone.insert("dosAndDonts".into(), json!({
-            "dos":["Use contractions","Prefer plain words","Keep it short and concrete","Focus on outcomes","Sound confident and helpful","Write like a person"],
-            "donts":["Corporate phrasing","Buzzwords","Grand metaphors","Overlong sentences","Exclamation marks","Empty filler"]
-        })); }
+        let traits = shared.get("toneTraits").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        let defaults = fallback::tone(fb, traits);
+        let mut tone = bg.get("tone").and_then(|v| v.as_object()).cloned().unwrap_or_default();
+        for key in ["traits", "description", "dosAndDonts"] {
+            if !tone.contains_key(key) { tone.insert(key.into(), defaults[key].clone()); }
+        }
         o.insert("tone".into(), V::Object(tone));
     }
     // taglines
@@ -226,23 +231,17 @@ fn deterministic_fill(current: &Value, shared: &Value, bg: &Value, me: &Value, c
         if let Some(arr) = cc.get("taglines").and_then(|v| v.as_array()) {
             for it in arr { if let (Some(tl), Some(r)) = (it.get("tagline"), it.get("rationale")) { tags.push(json!({"tagline": tl, "rationale": r})); } }
         }
-        // add defaults up to 3
-        let defaults = vec![
-            ("Built For Real Work", "Plain promise: useful software for day‑to‑day operations"),
-            ("Make It Work Better", "What clients want: smoother, faster, fewer headaches"),
-            ("Simple. Solid. Shipped.", "Conversational cadence with focus on delivery"),
-            ("North‑Ready Software", "A subtle nod to Canadian context; practical and reliable")
-        ];
-        for (t, r) in defaults {
+        // add template defaults up to 3
+        for t in fallback::taglines(fb) {
             if tags.len() >= 3 { break; }
-            tags.push(json!({"tagline": t, "rationale": r}));
+            tags.push(t);
         }
     } else {
         // ensure each has rationale
         for it in tags.iter_mut() {
             if it.get("rationale").and_then(|v| v.as_str()).unwrap_or("").is_empty() {
                 let tl = it.get("tagline").and_then(|v| v.as_str()).unwrap_or("");
-                let rationale = format!("Explains how '{}' reflects outcomes and tone.", tl);
+                let rationale = fallback::tagline_rationale(fb, tl);
                 if let Some(obj) = it.as_object_mut() { obj.insert("rationale".into(), json!(rationale)); }
             }
         }
@@ -251,25 +250,32 @@ fn deterministic_fill(current: &Value, shared: &Value, bg: &Value, me: &Value, c
     out
 }
 
-fn banlist() -> String { crate::prompts::BANNED.join(", ") }
+// Built-in list for the guide's language + per-guide banned terms, with the guide's preferred vocabulary appended when present
+fn banlist(inputs: &UserInputs, lang: &str, extra: &[String]) -> String {
+    let mut out = crate::prompts::banned_terms(lang, extra).join(", ");
+    let pref = crate::prompts::preferred_vocabulary(&inputs.preferredTerms);
+    if !pref.is_empty() { out.push_str(&format!(". Preferred vocabulary: {}", pref)); }
+    out
+}
 
-fn build_orchestrator_split_prompt(inputs: &UserInputs) -> String {
+fn build_orchestrator_split_prompt(inputs: &UserInputs, ban: &str) -> String {
     format!(
-        "Orchestrator (Pro) — Split Inputs\nAvoid banned buzzwords: {}\n\nUser Inputs:\n- Brand: {}\n- Industry: {}\n- Mission: {}\n- Audience: {}\n- Tone Traits: {}\n\nTask: Return STRICT JSON with keys: shared, bgBrief, meBrief, ccBrief, checklist.\n- shared must include: brandName, industry, mission, audience, toneTraits (array).\n- bgBrief: short guidance for Branding Guru.\n- meBrief: short guidance for Marketing Expert.\n- ccBrief: short guidance for Chief Copywriter.\n- checklist: Markdown with phases: discovery, analysis, conceptualization, composition, refinement/polish, delivery. Leave all unchecked.\n",
-        banlist(),
+        "Orchestrator (Pro) — Split Inputs\nAvoid banned buzzwords: {}\n\nUser Inputs:\n- Brand: {}\n- Industry: {}\n- Mission: {}\n- Audience: {}\n- Tone Traits: {}{}\n\nTask: Return STRICT JSON with keys: shared, bgBrief, meBrief, ccBrief, checklist.\n- shared must include: brandName, industry, mission, audience, toneTraits (array).\n- bgBrief: short guidance for Branding Guru.\n- meBrief: short guidance for Marketing Expert.\n- ccBrief: short guidance for Chief Copywriter.\n- checklist: Markdown with phases: discovery, analysis, conceptualization, composition, refinement/polish, delivery. Leave all unchecked.\n",
+        ban,
         inputs.brandName,
         inputs.industry,
         inputs.mission,
         inputs.audience,
-        inputs.toneTraits.join(", ")
+        inputs.toneTraits.join(", "),
+        inputs.region.as_deref().filter(|r| !r.trim().is_empty()).map(|r| format!("\n- Region: {}", r.trim())).unwrap_or_default()
     )
 }
 
 fn style_rules() -> &'static str {
-    "Style: Conversational, plainspoken, and friendly‑professional. Use contractions (we're, it's).\n- Write like you're talking to a Canadian small‑business owner.\n- Prefer short sentences (8–16 words).\n- Avoid corporate or academic tone.\n- No buzzwords or grand metaphors.\n- Keep lists tight and concrete.\n"
+    "Style: Conversational, plainspoken, and friendly‑professional. Use contractions (we're, it's).\n- Write like you're talking to one person from the target audience.\n- Prefer short sentences (8–16 words).\n- Avoid corporate or academic tone.\n- No buzzwords or grand metaphors.\n- Keep lists tight and concrete.\n"
 }
 
-fn build_bg_prompt(shared: &Value, brief: &str, checklist: &str) -> String {
+fn build_bg_prompt(shared: &Value, brief: &str, checklist: &str, ban: &str) -> String {
     let pre = "Chatroom: Collaborative roundtable. Participants: ORCH, BG, ME, CC, USER. Treat USER as a core stakeholder.";
     format!(
         "{}\nBranding Guru (Flash) — Tone & Guardrails\nShared: {}\nBrief: {}\nChecklist (read-only):\n{}\n\n{}\nDeliver STRICT JSON: {{ \"tone\": {{ \"traits\": [strings], \"description\": string (60–100 words, conversational; optionally include a simple analogy if it truly clarifies; do not label it), \"dosAndDonts\": {{ \"dos\":[5–6 short strings], \"donts\":[5–6 short strings] }} }} }}\nNo emojis/exclamations. Avoid banned buzzwords: {}\n",
@@ -278,11 +284,11 @@ fn build_bg_prompt(shared: &Value, brief: &str, checklist: &str) -> String {
         brief,
         checklist,
         style_rules(),
-        banlist()
+        ban
     )
 }
 
-fn build_me_prompt(shared: &Value, brief: &str, checklist: &str) -> String {
+fn build_me_prompt(shared: &Value, brief: &str, checklist: &str, ban: &str) -> String {
     let pre = "Chatroom: Collaborative roundtable. Participants: ORCH, BG, ME, CC, USER. Treat USER as a core stakeholder.";
     format!(
         "{}\nMarketing Expert (Flash) — Audience & Pitch Scaffold\nShared: {}\nBrief: {}\nChecklist (read-only):\n{}\n\n{}\nDeliver STRICT JSON: {{ \"audience\": string (2–3 sentences, plain language; cover who/need/triggers/objections), \"pitchNotes\": string (short, friendly, and concrete) }}\nAvoid banned buzzwords: {}\n",
@@ -291,11 +297,11 @@ fn build_me_prompt(shared: &Value, brief: &str, checklist: &str) -> String {
         brief,
         checklist,
         style_rules(),
-        banlist()
+        ban
     )
 }
 
-fn build_cc_prompt(shared: &Value, brief: &str, bg: &Value, me: &Value, checklist: &str, existing_tagline: Option<&str>) -> String {
+fn build_cc_prompt(shared: &Value, brief: &str, bg: &Value, me: &Value, checklist: &str, existing_tagline: Option<&str>, ban: &str) -> String {
     let pre = "Chatroom: Collaborative roundtable. Participants: ORCH, BG, ME, CC, USER. Treat USER as a core stakeholder.";
     let existing = existing_tagline.unwrap_or("");
     let existing_block = if existing.is_empty() { String::new() } else { format!("\nExisting user tagline: \"{}\"\nInclude it as one of the taglines with a rationale, then add two fresh options.", existing) };
@@ -309,7 +315,7 @@ fn build_cc_prompt(shared: &Value, brief: &str, bg: &Value, me: &Value, checklis
         checklist,
         style_rules(),
         existing_block,
-        banlist()
+        ban
     )
 }
 
@@ -346,64 +352,20 @@ async fn drain_user_notes(adapter: &AdapterDyn, store: &std::sync::Arc<tokio::sy
     None
 }
 
-fn fallback_bg_deliverable(shared: &Value) -> Value {
-    use serde_json::json;
+fn fallback_bg_deliverable(shared: &Value, fb: &fallback::Ctx) -> Value {
     let traits = shared.get("toneTraits").and_then(|v| v.as_array()).cloned().unwrap_or_default();
-    let brand = shared.get("brandName").and_then(|v| v.as_str()).unwrap_or("");
-    let tone_list: String = if traits.is_empty() { "friendly‑professional".to_string() } else { traits.iter().map(|t| t.as_str().unwrap_or("")).collect::<Vec<_>>().join(", ") };
-    let desc = format!("{} speaks in a clear, conversational voice—friendly, direct, and human. Short sentences. Plain language. If an analogy helps, keep it simple and practical.", brand);
-    json!({
-        "tone": {
-            "traits": traits,
-            "description": desc,
-            "dosAndDonts": {
-                "dos": [
-                    "Use contractions (we're, it's)",
-                    "Prefer plain words over jargon",
-                    "Keep sentences short and concrete",
-                    "Talk about outcomes customers care about",
-                    "Sound confident but approachable",
-                    "Write like a person, not a brochure"
-                ],
-                "donts": [
-                    "Corporate or academic phrasing",
-                    "Buzzwords and vague claims",
-                    "Grand metaphors or clichés",
-                    "Overlong sentences",
-                    "Exclamation marks",
-                    "Empty filler"
-                ]
-            }
-        }
-    })
+    serde_json::json!({"tone": fallback::tone(fb, traits)})
 }
 
-fn fallback_me_deliverable(shared: &Value) -> Value {
-    use serde_json::json;
-    let brand = shared.get("brandName").and_then(|v| v.as_str()).unwrap_or("");
-    let industry = shared.get("industry").and_then(|v| v.as_str()).unwrap_or("");
-    let audience = format!(
-        "{} serves Canadian organizations seeking dependable {} that translate into measurable results, with clear onboarding and support that reduce risk and complexity.",
-        brand, industry
-    );
-    let notes = "Focus on who/need/objections: highlight triggers (inefficiency, manual work), objections (budget, complexity), and show proof (case studies).";
-    json!({"audience": audience, "pitchNotes": notes})
+fn fallback_me_deliverable(fb: &fallback::Ctx) -> Value {
+    serde_json::json!({"audience": fallback::audience(fb), "pitchNotes": fallback::pitch_notes(fb)})
 }
 
-fn fallback_cc_deliverable(shared: &Value) -> Value {
-    use serde_json::json;
-    let brand = shared.get("brandName").and_then(|v| v.as_str()).unwrap_or("");
-    let mission = format!("We build useful software that helps Canadian businesses run better.");
-    let pitch = format!("At {}, we design and ship custom web apps, automation, and AI that save time and move the needle. You work with one senior developer who keeps things clear, reduces risk, and fits the build to how your team works.", brand);
-    let taglines = vec![
-        json!({"tagline": "Built For Real Work", "rationale": "Plain promise: useful software for day‑to‑day operations."}),
-        json!({"tagline": "Make It Work Better", "rationale": "Says what clients want: smoother, faster, fewer headaches."}),
-        json!({"tagline": "Simple. Solid. Shipped.", "rationale": "Conversational cadence with focus on delivery."})
-    ];
-    json!({"mission": mission, "elevatorPitch": pitch, "taglines": taglines})
+fn fallback_cc_deliverable(fb: &fallback::Ctx) -> Value {
+    serde_json::json!({"mission": fallback::mission(fb), "elevatorPitch": fallback::pitch(fb), "taglines": fallback::taglines(fb)})
 }
 
-async fn generate_with_retry(
+pub(crate) async fn generate_with_retry(
     adapter: &AdapterDyn,
     primary_model: &str,
     alt_model: &str,
@@ -446,10 +408,10 @@ async fn snapshot_user_notes(store: &std::sync::Arc<tokio::sync::Mutex<Vec<Strin
     Some(out)
 }
 
-fn build_orchestrator_assemble_prompt(shared: &Value, bg: &Value, me: &Value, cc: &Value) -> String {
+fn build_orchestrator_assemble_prompt(shared: &Value, bg: &Value, me: &Value, cc: &Value, ban: &str) -> String {
     format!(
         "Orchestrator (Pro) — Assemble Final JSON\nAvoid banned buzzwords: {}\nShared: {}\nBG: {}\nME: {}\nCC: {}\n\n{}\nTask: Merge into STRICT JSON with keys exactly: brandName, industry, mission, audience, tone{{traits, description, dosAndDonts{{dos, donts}}}}, taglines[{{tagline, rationale}}], elevatorPitch.\nUse shared.brandName and shared.industry directly. Use BG.tone. Use ME.audience. Use CC.mission, CC.elevatorPitch, CC.taglines. No extra keys.\n",
-        banlist(),
+        ban,
         serde_json::to_string_pretty(shared).unwrap_or_default(),
         serde_json::to_string_pretty(bg).unwrap_or_default(),
         serde_json::to_string_pretty(me).unwrap_or_default(),
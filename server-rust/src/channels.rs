@@ -0,0 +1,136 @@
+// Built-in channel profiles for rewrites: hard character limit, emoji/hashtag policy and formatting rules.
+use serde::Serialize;
+
+use crate::models::RewriteOptions;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelProfile {
+    pub id: &'static str,
+    pub label: &'static str,
+    // Hard limit in characters (Unicode scalar values)
+    pub maxChars: usize,
+    pub maxEmoji: usize,
+    pub maxHashtags: usize,
+    // Whether Markdown (headings, lists, bold, links) renders on the channel
+    pub markdown: bool,
+    pub singleLine: bool,
+    pub rules: &'static str,
+}
+
+pub const PROFILES: &[ChannelProfile] = &[
+    ChannelProfile { id: "x", label: "X/Twitter post", maxChars: 280, maxEmoji: 2, maxHashtags: 2, markdown: false, singleLine: false, rules: "One post, no thread. Plain text; put any hashtags at the end." },
+    ChannelProfile { id: "linkedin", label: "LinkedIn post", maxChars: 3000, maxEmoji: 3, maxHashtags: 5, markdown: false, singleLine: false, rules: "Plain text; short paragraphs separated by blank lines. Lead with the key point in the first two lines." },
+    ChannelProfile { id: "email_subject", label: "Email subject line", maxChars: 60, maxEmoji: 1, maxHashtags: 0, markdown: false, singleLine: true, rules: "One line, no trailing period, no ALL CAPS." },
+    ChannelProfile { id: "email_body", label: "Email body", maxChars: 2000, maxEmoji: 0, maxHashtags: 0, markdown: false, singleLine: false, rules: "Greeting, short paragraphs, one clear call to action, sign-off. Plain text." },
+    ChannelProfile { id: "push", label: "Push notification", maxChars: 178, maxEmoji: 1, maxHashtags: 0, markdown: false, singleLine: true, rules: "One or two short sentences; the first 40 characters must carry the message." },
+    ChannelProfile { id: "google_ads_headline", label: "Google Ads headline", maxChars: 30, maxEmoji: 0, maxHashtags: 0, markdown: false, singleLine: true, rules: "No exclamation marks, no emoji, no gimmicky capitalization or repeated punctuation." },
+    ChannelProfile { id: "google_ads_description", label: "Google Ads description", maxChars: 90, maxEmoji: 0, maxHashtags: 0, markdown: false, singleLine: true, rules: "At most one exclamation mark, no emoji, no gimmicky capitalization or repeated punctuation." },
+    ChannelProfile { id: "sms", label: "SMS", maxChars: 160, maxEmoji: 0, maxHashtags: 0, markdown: false, singleLine: false, rules: "Fits one GSM segment: no emoji or curly quotes. Include opt-out wording only if the original has it." },
+];
+
+/// Looks up a profile by id or common alias ("twitter", "tweet", "emailSubject", "ads-headline", ...).
+pub fn profile(name: &str) -> Option<&'static ChannelProfile> {
+    let key: String = name.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_lowercase();
+    let id = match key.as_str() {
+        "x" | "twitter" | "tweet" | "xpost" => "x",
+        "linkedin" | "linkedinpost" => "linkedin",
+        "emailsubject" | "subject" | "subjectline" => "email_subject",
+        "emailbody" | "email" => "email_body",
+        "push" | "pushnotification" | "notification" => "push",
+        "googleadsheadline" | "adsheadline" | "adheadline" => "google_ads_headline",
+        "googleadsdescription" | "adsdescription" | "addescription" => "google_ads_description",
+        "sms" | "text" | "textmessage" => "sms",
+        _ => return None,
+    };
+    PROFILES.iter().find(|p| p.id == id)
+}
+
+pub fn for_options(opts: Option<&RewriteOptions>) -> Option<&'static ChannelProfile> {
+    opts.and_then(|o| o.channel.as_deref()).and_then(profile)
+}
+
+/// System-prompt line describing the channel's limits.
+pub fn directive(p: &ChannelProfile) -> String {
+    let emoji = match p.maxEmoji { 0 => "no emoji".to_string(), n => format!("at most {} emoji", n) };
+    let tags = match p.maxHashtags { 0 => "no hashtags".to_string(), n => format!("at most {} hashtags", n) };
+    format!(
+        "\n- Channel: {}. Hard limit {} characters including spaces; {}; {}; {}{}. {}",
+        p.label, p.maxChars, emoji, tags,
+        if p.markdown { "Markdown allowed" } else { "no Markdown" },
+        if p.singleLine { "; a single line" } else { "" },
+        p.rules
+    )
+}
+
+/// Limit and policy violations of `text` for the channel (empty when it fits).
+pub fn check(p: &ChannelProfile, text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let t = text.trim();
+    let chars = t.chars().count();
+    if chars > p.maxChars {
+        out.push(format!("Channel {}: {} characters, limit is {}; cut {}", p.label, chars, p.maxChars, chars - p.maxChars));
+    }
+    let emoji = t.chars().filter(|c| is_emoji(*c)).count();
+    if emoji > p.maxEmoji {
+        out.push(format!("Channel {}: {} emoji, at most {} allowed", p.label, emoji, p.maxEmoji));
+    }
+    let tags = t.split_whitespace().filter(|w| w.len() > 1 && w.starts_with('#') && w[1..].chars().next().map(|c| c.is_alphanumeric()).unwrap_or(false)).count();
+    if tags > p.maxHashtags {
+        out.push(format!("Channel {}: {} hashtags, at most {} allowed", p.label, tags, p.maxHashtags));
+    }
+    if p.singleLine && t.contains('\n') {
+        out.push(format!("Channel {}: must be a single line", p.label));
+    }
+    if !p.markdown {
+        let s = crate::text::markdown::structure(t);
+        if !s.heading_levels.is_empty() || s.list_items > 0 || t.contains("**") || t.contains("](") {
+            out.push(format!("Channel {}: Markdown does not render here; use plain text", p.label));
+        }
+    }
+    out
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32, 0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_aliases_and_rejects_unknown_channels() {
+        assert_eq!(profile("Twitter").map(|p| p.id), Some("x"));
+        assert_eq!(profile("email-subject").map(|p| p.id), Some("email_subject"));
+        assert_eq!(profile("Ads_Headline").map(|p| p.id), Some("google_ads_headline"));
+        assert!(profile("myspace").is_none());
+        assert!(profile("").is_none());
+        let opts: RewriteOptions = serde_json::from_value(serde_json::json!({"channel": "fax"})).unwrap();
+        assert!(for_options(Some(&opts)).is_none());
+    }
+
+    #[test]
+    fn checks_each_limit() {
+        let x = profile("x").unwrap();
+        assert!(check(x, "  Plain post, well under the limit. #brand  ").is_empty());
+        // Limits count characters, not bytes
+        assert!(check(x, &"é".repeat(280)).is_empty());
+        let long = check(x, &"a".repeat(281));
+        assert_eq!(long, vec!["Channel X/Twitter post: 281 characters, limit is 280; cut 1"]);
+        assert_eq!(check(x, "Go 🚀🚀🚀").len(), 1);
+        assert!(check(x, "#one #two #three")[0].contains("3 hashtags"));
+
+        let sms = profile("sms").unwrap();
+        // A lone "#" is not a hashtag
+        assert!(check(sms, "Reply # to stop").is_empty());
+        assert!(check(sms, "Sale on #shoes")[0].contains("1 hashtags, at most 0"));
+
+        let subject = profile("subject").unwrap();
+        assert_eq!(check(subject, "Line one\nLine two"), vec!["Channel Email subject line: must be a single line"]);
+        let linkedin = profile("linkedin").unwrap();
+        assert!(check(linkedin, "Read **this**")[0].contains("Markdown"));
+        assert!(check(linkedin, "- item one\n- item two")[0].contains("Markdown"));
+    }
+}
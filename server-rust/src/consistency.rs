@@ -0,0 +1,35 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::{adapters::AdapterDyn, models::BrandGuide, prompts};
+
+/// Model score/feedback for `text` against the guide, with deterministic lint and readability findings attached.
+/// The text's language (explicit `locale`, else detected) selects the guide's locale rules.
+pub async fn check(adapter: &AdapterDyn, text: &str, guide: &BrandGuide, locale: Option<&str>) -> Result<Value> {
+    let (tag, language) = crate::locales::resolve(text, guide, locale);
+    let guide = match tag.as_deref() { Some(t) => crate::locales::localize(guide, t), None => guide.clone() };
+    let lang = tag.as_deref().unwrap_or("en");
+    let english = crate::text::lang::primary(lang) == "en";
+    let mut prompt = prompts::build_consistency_prompt(text, &guide, lang);
+    if !english { prompt.push_str(&prompts::language_line(lang)); }
+    prompt.push_str(&crate::exemplars::prompt_block(&guide, text));
+    let schema = crate::adapters::schemas::consistency_schema();
+    let mut data = adapter.generate_json(&prompt, Some(schema), Some(0.3)).await?;
+    // Deterministic findings ride alongside the model's score. The guide's long-sentence threshold drives both
+    // the lint rule and the readability share; the resolved language turns off English-only lint rules.
+    let opts = crate::models::LintOptions {
+        maxSentenceWords: guide.readability.as_ref().and_then(|r| r.longSentenceWords),
+        exclamations: None,
+        passiveVoice: None,
+        language: Some(lang.to_string()),
+    };
+    let lint = crate::text::lint::lint(text, Some(&guide), Some(&opts));
+    // Flesch–Kincaid and the passive heuristic are English-only
+    let readability = if english { json!(crate::text::readability::report(text, guide.readability.as_ref())) } else { json!(null) };
+    if let Some(obj) = data.as_object_mut() {
+        obj.insert("lint".to_string(), json!(lint));
+        obj.insert("readability".to_string(), readability);
+        obj.insert("language".to_string(), language);
+    }
+    Ok(data)
+}
@@ -0,0 +1,77 @@
+// Guide exemplars: picking the pairs most similar to the text at hand and rendering them as few-shot
+// prompt context, plus appending new pairs to a stored guide.
+use serde_json::{json, Value};
+
+use crate::models::{BrandGuide, Exemplar};
+
+// Pairs included in a prompt, and characters kept per side
+const PROMPT_EXEMPLARS: usize = 3;
+const PROMPT_CHARS: usize = 600;
+// Oldest exemplars are dropped beyond this many per guide
+pub const MAX_PER_GUIDE: usize = 200;
+
+/// Up to `k` exemplars ranked by similarity of `text` to either side of the pair (ties keep guide order).
+pub fn select<'a>(guide: &'a BrandGuide, text: &str, k: usize) -> Vec<&'a Exemplar> {
+    let docs: Vec<&str> = guide.exemplars.iter().map(|e| e.onBrand.as_str())
+        .chain(guide.exemplars.iter().map(|e| e.offBrand.as_deref().unwrap_or("")))
+        .collect();
+    let s = crate::text::similarity::scores(text, &docs);
+    let n = guide.exemplars.len();
+    let mut ranked: Vec<(usize, f32)> = (0..n).map(|i| (i, s[i].max(s[n + i]))).collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(&b.0)));
+    ranked.into_iter().take(k).map(|(i, _)| &guide.exemplars[i]).collect()
+}
+
+/// Few-shot block for the prompt, or empty when the guide has no exemplars.
+pub fn prompt_block(guide: &BrandGuide, text: &str) -> String {
+    let picked = select(guide, text, PROMPT_EXEMPLARS);
+    if picked.is_empty() { return String::new(); }
+    let clip = |s: &str| { let t: String = s.trim().chars().take(PROMPT_CHARS).collect(); if t.len() < s.trim().len() { format!("{}…", t) } else { t } };
+    let mut out = String::from("\n\nApproved examples of this brand's voice (match their style, not their content):");
+    for (i, e) in picked.iter().enumerate() {
+        out.push_str(&format!("\nExample {}:", i + 1));
+        if let Some(off) = e.offBrand.as_deref().filter(|s| !s.trim().is_empty()) { out.push_str(&format!("\n  Off-brand: \"{}\"", clip(off))); }
+        out.push_str(&format!("\n  On-brand: \"{}\"", clip(&e.onBrand)));
+        if let Some(n) = e.note.as_deref().filter(|s| !s.trim().is_empty()) { out.push_str(&format!("\n  Why: {}", n.trim())); }
+    }
+    out
+}
+
+/// Appends pairs to a stored guide's `exemplars` (skipping empty and duplicate on-brand text) and returns how many were added.
+pub fn append(guide: &mut Value, pairs: Vec<Exemplar>) -> usize {
+    let mut list: Vec<Exemplar> = serde_json::from_value(guide["exemplars"].clone()).unwrap_or_default();
+    let mut added = 0;
+    for p in pairs {
+        if p.onBrand.trim().is_empty() || list.iter().any(|e| e.onBrand.trim() == p.onBrand.trim()) { continue; }
+        list.push(p);
+        added += 1;
+    }
+    if list.len() > MAX_PER_GUIDE { list.drain(..list.len() - MAX_PER_GUIDE); }
+    guide["exemplars"] = json!(list);
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_pairs_closest_to_the_text() {
+        let ex = |on: &str, off: Option<&str>| Exemplar { onBrand: on.into(), offBrand: off.map(|s| s.into()), note: None, source: None };
+        let mut guide: BrandGuide = serde_json::from_value(json!({
+            "brandName": "Acme", "industry": "Outdoor", "mission": "", "audience": "", "elevatorPitch": "",
+            "tone": {"traits": [], "description": "", "dosAndDonts": {"dos": [], "donts": []}}, "taglines": [], "palette": {}
+        })).unwrap();
+        assert_eq!(prompt_block(&guide, "anything"), "");
+        guide.exemplars = vec![
+            ex("Orders ship in two days.", Some("We leverage logistics synergies to expedite order fulfilment.")),
+            ex("Trails reopen Saturday. Bring water.", None),
+            ex("Refunds land within a week.", Some("Refund processing is handled by our finance team.")),
+        ];
+        let picked = select(&guide, "Our trails are closed until Saturday", 1);
+        assert_eq!(picked[0].onBrand, "Trails reopen Saturday. Bring water.");
+        let picked = select(&guide, "Your refund is being processed", 2);
+        assert_eq!(picked[0].onBrand, "Refunds land within a week.");
+        assert!(prompt_block(&guide, "refund").contains("Off-brand: \"Refund processing"));
+    }
+}
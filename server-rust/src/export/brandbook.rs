@@ -0,0 +1,211 @@
+// Brand book layout: cover, voice, messaging, palette swatches with contrast ratings, font pairings.
+use super::fonts::{self, Font};
+use super::image::Image;
+use super::outline::ordered_palette;
+use super::pdf::{Pdf, MARGIN, PAGE_H, PAGE_W};
+use crate::models::BrandGuide;
+use crate::palette::color::{self, Rgb};
+
+const INK: Rgb = (33, 33, 33);
+const MUTED: Rgb = (110, 110, 110);
+const RULE: Rgb = (210, 210, 210);
+const HEADER_FILL: Rgb = (240, 240, 240);
+const WHITE: Rgb = (255, 255, 255);
+const BLACK: Rgb = (0, 0, 0);
+
+pub fn render(guide: &BrandGuide, logo: Option<Image>) -> Vec<u8> {
+    let mut pdf = Pdf::new(&format!("{} brand book", guide.brandName));
+    let accent = guide.palette.get("primary").and_then(|h| color::parse_color(h)).unwrap_or(INK);
+    cover(&mut pdf, guide, logo, accent);
+    pdf.new_page();
+
+    section(&mut pdf, "Mission", accent);
+    body(&mut pdf, &guide.mission);
+    section(&mut pdf, "Audience", accent);
+    body(&mut pdf, &guide.audience);
+
+    section(&mut pdf, "Tone of voice", accent);
+    if !guide.tone.traits.is_empty() {
+        let w = pdf.content_width();
+        pdf.paragraph(MARGIN, w, Font::Bold, 11.0, INK, &guide.tone.traits.join(" · "));
+    }
+    body(&mut pdf, &guide.tone.description);
+    pdf.y -= 8.0;
+    let dd = &guide.tone.dosAndDonts;
+    let rows: Vec<Vec<String>> = (0..dd.dos.len().max(dd.donts.len()))
+        .map(|i| vec![dd.dos.get(i).cloned().unwrap_or_default(), dd.donts.get(i).cloned().unwrap_or_default()])
+        .collect();
+    table(&mut pdf, &["Do", "Don't"], &[0.5, 0.5], &rows);
+
+    section(&mut pdf, "Taglines", accent);
+    let rows: Vec<Vec<String>> = guide.taglines.iter().map(|t| vec![t.tagline.clone(), t.rationale.clone()]).collect();
+    table(&mut pdf, &["Tagline", "Rationale"], &[0.35, 0.65], &rows);
+
+    section(&mut pdf, "Elevator pitch", accent);
+    body(&mut pdf, &guide.elevatorPitch);
+
+    section(&mut pdf, "Color palette", accent);
+    swatches(&mut pdf, guide);
+
+    section(&mut pdf, "Font pairings", accent);
+    font_pairings(&mut pdf, guide);
+
+    let brand = guide.brandName.clone();
+    pdf.finish(move |page, total| format!("{} brand book  ·  {} / {}", brand, page, total))
+}
+
+fn cover(pdf: &mut Pdf, guide: &BrandGuide, logo: Option<Image>, accent: Rgb) {
+    pdf.rect(0.0, PAGE_H - 12.0, PAGE_W, 12.0, Some(accent), None);
+    let mut y = PAGE_H - 140.0;
+    if let Some(img) = logo {
+        // Fit within 220 × 120 pt, centered
+        let scale = (220.0 / img.width as f32).min(120.0 / img.height as f32);
+        let (w, h) = (img.width as f32 * scale, img.height as f32 * scale);
+        pdf.image(img, (PAGE_W - w) / 2.0, y - h + 60.0, w, h);
+        y -= h;
+    }
+    y -= 40.0;
+    centered(pdf, y, Font::Bold, 30.0, INK, &guide.brandName);
+    y -= 24.0;
+    centered(pdf, y, Font::Regular, 13.0, MUTED, &guide.industry);
+    if let Some(t) = guide.taglines.first() {
+        y -= 36.0;
+        centered(pdf, y, Font::Oblique, 15.0, accent, &t.tagline);
+    }
+    pdf.y = y - 40.0;
+    let (x, w) = (MARGIN + 60.0, PAGE_W - 2.0 * (MARGIN + 60.0));
+    for line in fonts::wrap(Font::Regular, 12.0, &guide.mission, w) {
+        pdf.y -= 18.0;
+        let lw = fonts::text_width(Font::Regular, 12.0, &line);
+        pdf.text(x + (w - lw) / 2.0, pdf.y, Font::Regular, 12.0, INK, &line);
+    }
+    centered(pdf, MARGIN + 40.0, Font::Regular, 10.0, MUTED, "Brand guidelines");
+}
+
+fn centered(pdf: &mut Pdf, y: f32, font: Font, size: f32, color: Rgb, s: &str) {
+    let w = fonts::text_width(font, size, s);
+    pdf.text((PAGE_W - w) / 2.0, y, font, size, color, s);
+}
+
+fn section(pdf: &mut Pdf, title: &str, accent: Rgb) {
+    pdf.ensure(60.0);
+    pdf.y -= 30.0;
+    pdf.text(MARGIN, pdf.y, Font::Bold, 16.0, INK, title);
+    pdf.y -= 6.0;
+    pdf.line(MARGIN, pdf.y, MARGIN + 40.0, pdf.y, accent, 2.0);
+    pdf.y -= 4.0;
+}
+
+fn body(pdf: &mut Pdf, text: &str) {
+    let w = pdf.content_width();
+    pdf.paragraph(MARGIN, w, Font::Regular, 11.0, INK, text);
+}
+
+/// Bordered table with a shaded header; rows never split across pages.
+fn table(pdf: &mut Pdf, headers: &[&str], fractions: &[f32], rows: &[Vec<String>]) {
+    let (size, pad) = (10.0, 6.0);
+    let leading = size * 1.35;
+    let widths: Vec<f32> = fractions.iter().map(|f| f * pdf.content_width()).collect();
+    let draw_row = |pdf: &mut Pdf, cells: &[String], font: Font, fill: Option<Rgb>| {
+        let wrapped: Vec<Vec<String>> = cells.iter().zip(&widths).map(|(c, w)| fonts::wrap(font, size, c, w - 2.0 * pad)).collect();
+        let lines = wrapped.iter().map(|l| l.len()).max().unwrap_or(1).max(1);
+        let h = lines as f32 * leading + 2.0 * pad;
+        pdf.ensure(h);
+        let top = pdf.y;
+        let mut x = MARGIN;
+        for (cell, w) in wrapped.iter().zip(&widths) {
+            pdf.rect(x, top - h, *w, h, fill, Some(RULE));
+            for (i, line) in cell.iter().enumerate() {
+                pdf.text(x + pad, top - pad - size - i as f32 * leading + 2.0, font, size, INK, line);
+            }
+            x += w;
+        }
+        pdf.y = top - h;
+    };
+    let header: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    draw_row(pdf, &header, Font::Bold, Some(HEADER_FILL));
+    for r in rows { draw_row(pdf, r, Font::Regular, None); }
+}
+
+fn swatches(pdf: &mut Pdf, guide: &BrandGuide) {
+    let background = guide.palette.get("background").and_then(|h| color::parse_color(h));
+    for (role, rgb) in ordered_palette(&guide.palette) {
+        let h = 56.0;
+        pdf.ensure(h + 10.0);
+        pdf.y -= h + 10.0;
+        pdf.rect(MARGIN, pdf.y, 72.0, h, Some(rgb), Some(RULE));
+        let x = MARGIN + 86.0;
+        let (hh, s, l) = color::rgb_to_hsl(rgb);
+        pdf.text(x, pdf.y + h - 12.0, Font::Bold, 11.0, INK, &role);
+        let spec = format!("{}   RGB {}, {}, {}   HSL {:.0}°, {:.0}%, {:.0}%", color::to_hex(rgb), rgb.0, rgb.1, rgb.2, hh, s, l);
+        pdf.text(x, pdf.y + h - 27.0, Font::Regular, 9.5, INK, &spec);
+        let rate = |other: Rgb| { let r = color::contrast_ratio(rgb, other); format!("{:.1}:1 {}", r, color::wcag_rating(r)) };
+        let mut contrast = format!("White text {}   ·   Black text {}", rate(WHITE), rate(BLACK));
+        if let Some(bg) = background.filter(|_| role != "background") { contrast.push_str(&format!("   ·   On background {}", rate(bg))); }
+        pdf.text(x, pdf.y + h - 41.0, Font::Regular, 9.0, MUTED, &contrast);
+    }
+}
+
+fn font_pairings(pdf: &mut Pdf, guide: &BrandGuide) {
+    if guide.fontPairings.is_empty() {
+        body(pdf, "No font pairings defined yet.");
+        return;
+    }
+    for p in &guide.fontPairings {
+        pdf.ensure(70.0);
+        let name = p.name.clone().unwrap_or_else(|| format!("{} + {}", family(&p.heading), family(&p.body)));
+        pdf.y -= 20.0;
+        pdf.text(MARGIN, pdf.y, Font::Bold, 11.0, INK, &name);
+        pdf.y -= 14.0;
+        pdf.text(MARGIN, pdf.y, Font::Regular, 9.0, MUTED, &format!("Heading: {}   ·   Body: {}", p.heading, p.body));
+        // Samples use the closest built-in PDF face, since the brand fonts are not embedded
+        pdf.y -= 20.0;
+        pdf.text(MARGIN, pdf.y, builtin_face(&p.heading, true), 16.0, INK, &guide.brandName);
+        pdf.y -= 15.0;
+        let sample = guide.taglines.first().map(|t| t.tagline.as_str()).unwrap_or(&guide.mission);
+        let line = fonts::wrap(Font::Regular, 10.0, sample, pdf.content_width()).into_iter().next().unwrap_or_default();
+        pdf.text(MARGIN, pdf.y, builtin_face(&p.body, false), 10.0, INK, &line);
+    }
+}
+
+// First family name in a CSS font-family stack
+fn family(stack: &str) -> String {
+    stack.split(',').next().unwrap_or(stack).trim().trim_matches(['"', '\'']).to_string()
+}
+
+fn builtin_face(stack: &str, bold: bool) -> Font {
+    let s = stack.to_lowercase();
+    if s.contains("mono") { Font::Mono }
+    else if s.contains("serif") && !s.contains("sans-serif") { if bold { Font::SerifBold } else { Font::Serif } }
+    else if bold { Font::Bold } else { Font::Regular }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::*;
+
+    #[test]
+    fn renders_pdf_with_valid_xref() {
+        let guide: BrandGuide = serde_json::from_value(serde_json::json!({
+            "brandName": "Harbor & Pine", "industry": "Dental care", "mission": "Calm, honest dental care for families.",
+            "audience": "Families in the valley.", "elevatorPitch": "We keep visits short and explain every step.",
+            "tone": {"traits": ["Warm", "Plain"], "description": "Friendly and direct — never clinical.", "dosAndDonts": {"dos": ["Use first names"], "donts": ["Jargon", "Exclamation marks"]}},
+            "taglines": [{"tagline": "Smile (really)", "rationale": "Light, human"}],
+            "palette": {"primary": "#1E6B52", "background": "#FAFAF7", "text": "#222222"},
+            "fontPairings": [{"heading": "Lora, serif", "body": "Inter, sans-serif"}]
+        })).unwrap();
+        let pdf = render(&guide, None);
+        assert!(pdf.starts_with(b"%PDF-1.4") && pdf.ends_with(b"%%EOF\n"));
+        // Byte offsets matter, so only the ASCII tail is decoded
+        let tail_at = pdf.windows(9).rposition(|w| w == b"startxref").unwrap();
+        let xref: usize = std::str::from_utf8(&pdf[tail_at + 9..]).unwrap().split_whitespace().next().unwrap().parse().unwrap();
+        let table = std::str::from_utf8(&pdf[xref..]).unwrap();
+        assert!(table.starts_with("xref"));
+        // Every xref entry points at the matching "N 0 obj"
+        for (i, line) in table.lines().skip(3).take_while(|l| l.ends_with(" n ")).enumerate() {
+            let off: usize = line[..10].parse().unwrap();
+            assert!(pdf[off..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+}
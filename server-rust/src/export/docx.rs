@@ -0,0 +1,142 @@
+// Word (.docx) rendering of the guide outline: a hand-written WordprocessingML package with
+// built-in heading styles, bordered tables and PNG swatches. No timestamps, so output is stable.
+use super::image::swatch_png;
+use super::outline::{outline, Block, Cell};
+use super::zip::ZipWriter;
+use crate::models::BrandGuide;
+use crate::palette::color::Rgb;
+
+// Letter page with 0.75in margins, in twentieths of a point
+const TEXT_WIDTH_TWIPS: usize = 12240 - 2 * 1080;
+// Swatch size in EMU (0.5in × 0.25in)
+const SWATCH_CX: u32 = 457_200;
+const SWATCH_CY: u32 = 228_600;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Default Extension="png" ContentType="image/png"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:cs="Calibri"/><w:sz w:val="22"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="120" w:line="276" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style><w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:spacing w:after="60"/></w:pPr><w:rPr><w:b/><w:sz w:val="52"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Subtitle"><w:name w:val="Subtitle"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:rPr><w:i/><w:color w:val="6E6E6E"/><w:sz w:val="26"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="360" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="32"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Quote"><w:name w:val="Quote"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:ind w:left="720"/></w:pPr><w:rPr><w:i/><w:sz w:val="28"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="ListBullet"><w:name w:val="List Bullet"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="40"/><w:ind w:left="360" w:hanging="360"/></w:pPr></w:style><w:style w:type="table" w:styleId="TableGrid"><w:name w:val="Table Grid"/><w:tblPr><w:tblBorders><w:top w:val="single" w:sz="4" w:color="D2D2D2"/><w:left w:val="single" w:sz="4" w:color="D2D2D2"/><w:bottom w:val="single" w:sz="4" w:color="D2D2D2"/><w:right w:val="single" w:sz="4" w:color="D2D2D2"/><w:insideH w:val="single" w:sz="4" w:color="D2D2D2"/><w:insideV w:val="single" w:sz="4" w:color="D2D2D2"/></w:tblBorders><w:tblCellMar><w:top w:w="60" w:type="dxa"/><w:left w:w="100" w:type="dxa"/><w:bottom w:w="60" w:type="dxa"/><w:right w:w="100" w:type="dxa"/></w:tblCellMar></w:tblPr></w:style></w:styles>"#;
+
+pub fn render(guide: &BrandGuide) -> Vec<u8> {
+    let mut body = String::new();
+    let mut swatches: Vec<Rgb> = Vec::new();
+    let mut drawings = 0;
+    for block in outline(guide) {
+        match block {
+            Block::Title(t) => body.push_str(&para(Some("Title"), &run(&t, ""))),
+            Block::Subtitle(t) => body.push_str(&para(Some("Subtitle"), &run(&t, ""))),
+            Block::Heading(t) => body.push_str(&para(Some("Heading1"), &run(&t, ""))),
+            Block::Paragraph(t) => body.push_str(&para(None, &run(&t, ""))),
+            Block::Strong(t) => body.push_str(&para(None, &run(&t, "<w:b/>"))),
+            Block::Quote(t) => body.push_str(&para(Some("Quote"), &run(&t, ""))),
+            Block::Bullets(items) => for i in items { body.push_str(&para(Some("ListBullet"), &run(&format!("•\t{}", i), ""))); },
+            Block::Table { headers, rows } => {
+                if rows.is_empty() { body.push_str(&para(None, &run("None yet.", "<w:i/>"))); continue; }
+                body.push_str(&table(&headers, &rows, &mut swatches, &mut drawings));
+            }
+        }
+    }
+
+    let mut rels = String::from(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>"#);
+    for i in 1..=swatches.len() {
+        rels.push_str(&format!(r#"<Relationship Id="rIdSwatch{i}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/swatch{i}.png"/>"#));
+    }
+    rels.push_str("</Relationships>");
+    let document = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture"><w:body>{}<w:sectPr><w:pgSz w:w="12240" w:h="15840"/><w:pgMar w:top="1080" w:right="1080" w:bottom="1080" w:left="1080" w:header="720" w:footer="720" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+        body
+    );
+
+    let mut zip = ZipWriter::default();
+    zip.add("[Content_Types].xml", CONTENT_TYPES.as_bytes());
+    zip.add("_rels/.rels", ROOT_RELS.as_bytes());
+    zip.add("word/document.xml", document.as_bytes());
+    zip.add("word/styles.xml", STYLES.as_bytes());
+    zip.add("word/_rels/document.xml.rels", rels.as_bytes());
+    for (i, rgb) in swatches.iter().enumerate() { zip.add(&format!("word/media/swatch{}.png", i + 1), &swatch_png(*rgb, 32, 16)); }
+    zip.finish()
+}
+
+fn para(style: Option<&str>, runs: &str) -> String {
+    match style {
+        Some(s) => format!(r#"<w:p><w:pPr><w:pStyle w:val="{}"/></w:pPr>{}</w:p>"#, s, runs),
+        None => format!("<w:p>{}</w:p>", runs),
+    }
+}
+
+// Line breaks in the text become <w:br/>; `rpr` is extra run properties such as <w:b/>
+fn run(text: &str, rpr: &str) -> String {
+    let lines: Vec<String> = text.lines().map(|l| format!(r#"<w:t xml:space="preserve">{}</w:t>"#, escape(l))).collect();
+    let tabbed = lines.join("<w:br/>").replace('\t', r#"</w:t><w:tab/><w:t xml:space="preserve">"#);
+    format!("<w:r><w:rPr>{}</w:rPr>{}</w:r>", rpr, tabbed)
+}
+
+// Swatch images are stored once per color; every drawing still needs its own docPr id
+fn table(headers: &[String], rows: &[Vec<Cell>], swatches: &mut Vec<Rgb>, drawings: &mut usize) -> String {
+    let col = TEXT_WIDTH_TWIPS / headers.len().max(1);
+    let mut out = String::from(r#"<w:tbl><w:tblPr><w:tblStyle w:val="TableGrid"/><w:tblW w:w="5000" w:type="pct"/></w:tblPr><w:tblGrid>"#);
+    for _ in headers { out.push_str(&format!(r#"<w:gridCol w:w="{}"/>"#, col)); }
+    out.push_str("</w:tblGrid><w:tr><w:trPr><w:tblHeader/></w:trPr>");
+    for h in headers {
+        out.push_str(&format!(r#"<w:tc><w:tcPr><w:tcW w:w="{}" w:type="dxa"/><w:shd w:val="clear" w:color="auto" w:fill="F0F0F0"/></w:tcPr>{}</w:tc>"#, col, para(None, &run(h, r#"<w:b/><w:sz w:val="18"/>"#))));
+    }
+    out.push_str("</w:tr>");
+    for r in rows {
+        out.push_str(r#"<w:tr><w:trPr><w:cantSplit/></w:trPr>"#);
+        for c in r {
+            let content = match c {
+                Cell::Text(s) => run(s, r#"<w:sz w:val="18"/>"#),
+                Cell::Swatch(rgb) => {
+                    let media = match swatches.iter().position(|s| s == rgb) { Some(i) => i + 1, None => { swatches.push(*rgb); swatches.len() } };
+                    *drawings += 1;
+                    drawing(*drawings, media, &c.text())
+                }
+            };
+            out.push_str(&format!(r#"<w:tc><w:tcPr><w:tcW w:w="{}" w:type="dxa"/></w:tcPr>{}</w:tc>"#, col, para(None, &content)));
+        }
+        out.push_str("</w:tr>");
+    }
+    // Word requires a paragraph between adjacent tables
+    out.push_str("</w:tbl><w:p/>");
+    out
+}
+
+fn drawing(id: usize, media: usize, descr: &str) -> String {
+    format!(
+        r#"<w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0"><wp:extent cx="{cx}" cy="{cy}"/><wp:docPr id="{id}" name="Swatch {id}" descr="{descr}"/><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture"><pic:pic><pic:nvPicPr><pic:cNvPr id="{id}" name="swatch{media}.png"/><pic:cNvPicPr/></pic:nvPicPr><pic:blipFill><a:blip r:embed="rIdSwatch{media}"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill><pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"#,
+        cx = SWATCH_CX, cy = SWATCH_CY, id = id, media = media, descr = escape(descr)
+    )
+}
+
+// XML 1.0 forbids most control characters, so they are dropped
+fn escape(s: &str) -> String {
+    s.chars().filter(|c| !c.is_control() || *c == '\t').collect::<String>().replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_is_deterministic_and_lists_every_part() {
+        let guide: BrandGuide = serde_json::from_value(serde_json::json!({
+            "brandName": "Harbor & Pine", "industry": "Dental care", "mission": "Calm <honest> care.", "audience": "Families.",
+            "elevatorPitch": "Short visits.", "tone": {"traits": ["Warm"], "description": "Direct.", "dosAndDonts": {"dos": ["Explain"], "donts": []}},
+            "taglines": [], "palette": {"primary": "#1E6B52", "text": "#1E6B52", "background": "nope"}
+        })).unwrap();
+        let a = render(&guide);
+        assert_eq!(a, render(&guide));
+        // End of central directory: entry count at offset 10, directory offset at 16
+        let eocd = a.len() - 22;
+        assert_eq!(&a[eocd..eocd + 4], &0x06054b50u32.to_le_bytes());
+        assert_eq!(u16::from_le_bytes([a[eocd + 10], a[eocd + 11]]), 6); // five parts + one shared swatch
+        let cd = u32::from_le_bytes(a[eocd + 16..eocd + 20].try_into().unwrap()) as usize;
+        assert_eq!(&a[cd..cd + 4], &0x02014b50u32.to_le_bytes());
+    }
+}
@@ -0,0 +1,100 @@
+// Standard 14 PDF fonts: WinAnsi encoding and Helvetica advance widths (1/1000 em) for line wrapping.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font { Regular, Bold, Oblique, Serif, SerifBold, Mono }
+
+impl Font {
+    pub fn resource(self) -> &'static str {
+        match self { Font::Regular => "F1", Font::Bold => "F2", Font::Oblique => "F3", Font::Serif => "F4", Font::SerifBold => "F5", Font::Mono => "F6" }
+    }
+    pub const ALL: [(Font, &'static str); 6] = [
+        (Font::Regular, "Helvetica"), (Font::Bold, "Helvetica-Bold"), (Font::Oblique, "Helvetica-Oblique"),
+        (Font::Serif, "Times-Roman"), (Font::SerifBold, "Times-Bold"), (Font::Mono, "Courier"),
+    ];
+}
+
+// Widths for ' '..='~'
+const HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+const HELVETICA_BOLD: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Width of `s` in points. Times is approximated with Helvetica metrics (slightly wide, so wrapping stays safe).
+pub fn text_width(font: Font, size: f32, s: &str) -> f32 {
+    if font == Font::Mono { return s.chars().count() as f32 * 600.0 * size / 1000.0; }
+    let table = if matches!(font, Font::Bold | Font::SerifBold) { &HELVETICA_BOLD } else { &HELVETICA };
+    let units: u32 = s.chars().map(|c| match c {
+        ' '..='~' => table[c as usize - 32] as u32,
+        // Accented letters and typographic punctuation: close enough to a lowercase letter
+        _ => 556,
+    }).sum();
+    units as f32 * size / 1000.0
+}
+
+/// WinAnsiEncoding byte for `c`; anything outside it becomes '?'.
+pub fn win_ansi(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '\u{A0}'..='\u{FF}' => c as u32 as u8,
+        '€' => 0x80, '‚' => 0x82, 'ƒ' => 0x83, '„' => 0x84, '…' => 0x85, '†' => 0x86, '‡' => 0x87,
+        'ˆ' => 0x88, '‰' => 0x89, 'Š' => 0x8A, '‹' => 0x8B, 'Œ' => 0x8C, 'Ž' => 0x8E,
+        '‘' => 0x91, '’' => 0x92, '“' => 0x93, '”' => 0x94, '•' => 0x95, '–' => 0x96, '—' => 0x97,
+        '˜' => 0x98, '™' => 0x99, 'š' => 0x9A, '›' => 0x9B, 'œ' => 0x9C, 'ž' => 0x9E, 'Ÿ' => 0x9F,
+        // Non-breaking/figure hyphens and narrow spaces used by the generators
+        '\u{2010}' | '\u{2011}' | '\u{2012}' => b'-',
+        '\u{2009}' | '\u{202F}' | '\u{2007}' => b' ',
+        _ => b'?',
+    }
+}
+
+/// PDF literal string `(…)` in WinAnsi, with delimiters escaped and high bytes as octal.
+pub fn pdf_string(s: &str) -> Vec<u8> {
+    let mut out = vec![b'('];
+    for c in s.chars() {
+        match win_ansi(c) {
+            b @ (b'(' | b')' | b'\\') => { out.push(b'\\'); out.push(b); }
+            b if b >= 0x80 => out.extend(format!("\\{:03o}", b).bytes()),
+            b => out.push(b),
+        }
+    }
+    out.push(b')');
+    out
+}
+
+/// Greedy word wrap to `width` points; words longer than a line are broken by character.
+pub fn wrap(font: Font, size: f32, text: &str, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for para in text.split('\n') {
+        let mut line = String::new();
+        for word in para.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if text_width(font, size, &candidate) <= width { line = candidate; continue; }
+            if !line.is_empty() { lines.push(std::mem::take(&mut line)); }
+            // Hard-break overlong words
+            let mut piece = String::new();
+            for c in word.chars() {
+                piece.push(c);
+                if text_width(font, size, &piece) > width && piece.chars().count() > 1 {
+                    let last = piece.pop().unwrap_or(' ');
+                    lines.push(std::mem::take(&mut piece));
+                    piece.push(last);
+                }
+            }
+            line = piece;
+        }
+        lines.push(line);
+    }
+    lines
+}
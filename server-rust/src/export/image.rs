@@ -0,0 +1,254 @@
+// Logo loading for exports: data: URLs or http(s), decoded to something a PDF can embed.
+// JPEG passes through (DCTDecode); 8-bit PNG is unfiltered into RGB plus an optional alpha mask. SVG is not supported.
+use std::io::Read;
+
+const MAX_BYTES: usize = 5 * 1024 * 1024;
+const MAX_PIXELS: u64 = 16_000_000;
+
+pub enum ImageData {
+    Jpeg { data: Vec<u8>, components: u8 },
+    Raw { rgb: Vec<u8>, alpha: Option<Vec<u8>> },
+}
+
+pub struct Image { pub width: u32, pub height: u32, pub data: ImageData }
+
+/// Fetches and decodes the logo; None when missing, unsupported or unreachable (the export renders without it).
+/// Remote logos must resolve to public addresses and are fetched without following redirects.
+pub async fn load(url: &str) -> Option<Image> {
+    let url = url.trim();
+    let bytes = if let Some(rest) = url.strip_prefix("data:") {
+        let (meta, payload) = rest.split_once(',')?;
+        if !meta.ends_with(";base64") { return None; }
+        // Base64 is 4 chars per 3 bytes; refuse before decoding anything larger than MAX_BYTES
+        let payload = payload.trim();
+        if payload.len() / 4 * 3 > MAX_BYTES { return None; }
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.decode(payload).ok()?
+    } else if url.starts_with("http://") || url.starts_with("https://") {
+        fetch(url).await?
+    } else {
+        return None;
+    };
+    let img = decode(&bytes);
+    if img.is_none() { tracing::info!("logo at {} is not a supported PNG/JPEG; exporting without it", if url.starts_with("data:") { "data: URL" } else { url }); }
+    img
+}
+
+// The client posts the guide, so the URL is untrusted: only public hosts, no redirects, at most MAX_BYTES
+async fn fetch(url: &str) -> Option<Vec<u8>> {
+    let parsed = reqwest::Url::parse(url).ok()?;
+    let host = parsed.host_str()?.trim_start_matches('[').trim_end_matches(']').to_string();
+    let port = parsed.port_or_known_default()?;
+    let addrs: Vec<std::net::SocketAddr> = tokio::net::lookup_host((host.as_str(), port)).await.ok()?.collect();
+    if addrs.is_empty() || !addrs.iter().all(|a| is_public(a.ip())) {
+        tracing::warn!("logo host {} does not resolve to a public address; exporting without it", host);
+        return None;
+    }
+    // Pin the checked addresses so a second lookup can't point the request somewhere else
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve_to_addrs(&host, &addrs)
+        .build().ok()?;
+    let mut resp = client.get(parsed).send().await.ok()?;
+    if !resp.status().is_success() { return None; }
+    if resp.content_length().is_some_and(|n| n > MAX_BYTES as u64) { return None; }
+    let mut body = Vec::new();
+    while let Some(chunk) = resp.chunk().await.ok()? {
+        if body.len() + chunk.len() > MAX_BYTES { return None; }
+        body.extend_from_slice(&chunk);
+    }
+    Some(body)
+}
+
+fn is_public(ip: std::net::IpAddr) -> bool {
+    use std::net::IpAddr;
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            let shared = a == 100 && (64..128).contains(&b); // 100.64.0.0/10 carrier-grade NAT
+            !(v4.is_private() || v4.is_loopback() || v4.is_link_local() || v4.is_unspecified() || v4.is_broadcast()
+                || v4.is_multicast() || v4.is_documentation() || shared || a == 0)
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public(IpAddr::V4(v4)),
+            None => !(v6.is_loopback() || v6.is_unspecified() || v6.is_multicast() || v6.is_unique_local() || v6.is_unicast_link_local()),
+        },
+    }
+}
+
+pub fn decode(bytes: &[u8]) -> Option<Image> {
+    if bytes.starts_with(&[0xFF, 0xD8]) { return decode_jpeg(bytes); }
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") { return decode_png(bytes); }
+    None
+}
+
+// Dimensions and component count from the SOF marker; the data itself is embedded as-is
+fn decode_jpeg(bytes: &[u8]) -> Option<Image> {
+    let mut i = 2;
+    while i + 9 < bytes.len() {
+        if bytes[i] != 0xFF { i += 1; continue; }
+        let marker = bytes[i + 1];
+        if marker == 0xFF || marker == 0x01 || (0xD0..=0xD7).contains(&marker) { i += 1; continue; }
+        let len = u16::from_be_bytes([bytes[i + 2], bytes[i + 3]]) as usize;
+        if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            let height = u16::from_be_bytes([bytes[i + 5], bytes[i + 6]]) as u32;
+            let width = u16::from_be_bytes([bytes[i + 7], bytes[i + 8]]) as u32;
+            let components = bytes[i + 9];
+            // CMYK JPEGs need Adobe-specific inversion handling; skip them
+            if !matches!(components, 1 | 3) || width == 0 || height == 0 { return None; }
+            return Some(Image { width, height, data: ImageData::Jpeg { data: bytes.to_vec(), components } });
+        }
+        i += 2 + len;
+    }
+    None
+}
+
+fn decode_png(bytes: &[u8]) -> Option<Image> {
+    let (mut width, mut height, mut ctype) = (0u32, 0u32, 0u8);
+    let (mut palette, mut trns, mut idat) = (Vec::new(), Vec::new(), Vec::new());
+    let mut i = 8;
+    while i + 8 <= bytes.len() {
+        let len = u32::from_be_bytes(bytes[i..i + 4].try_into().ok()?) as usize;
+        let kind = &bytes[i + 4..i + 8];
+        let data = bytes.get(i + 8..i + 8 + len)?;
+        match kind {
+            b"IHDR" => {
+                if data.len() < 13 { return None; }
+                width = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?);
+                height = u32::from_be_bytes(data.get(4..8)?.try_into().ok()?);
+                ctype = *data.get(9)?;
+                // 8-bit, non-interlaced only
+                if data.get(8) != Some(&8) || data.get(12) != Some(&0) { return None; }
+            }
+            b"PLTE" => palette = data.to_vec(),
+            b"tRNS" => trns = data.to_vec(),
+            b"IDAT" => idat.extend_from_slice(data),
+            b"IEND" => break,
+            _ => {}
+        }
+        i += 12 + len;
+    }
+    let channels = match ctype { 0 | 3 => 1, 4 => 2, 2 => 3, 6 => 4, _ => return None };
+    if width == 0 || height == 0 || width as u64 * height as u64 > MAX_PIXELS { return None; }
+    let stride = width as usize * channels;
+    let expected = (stride + 1) * height as usize;
+    let mut raw = Vec::with_capacity(expected);
+    // A few KB of IDAT can inflate to gigabytes; read one byte past the image size and reject anything longer
+    flate2::read::ZlibDecoder::new(&idat[..]).take(expected as u64 + 1).read_to_end(&mut raw).ok()?;
+    if raw.len() > expected { return None; }
+    let pixels = unfilter(&raw, stride, height as usize, channels)?;
+
+    let n = (width * height) as usize;
+    let mut rgb = Vec::with_capacity(n * 3);
+    let mut alpha = Vec::with_capacity(n);
+    for px in pixels.chunks(channels) {
+        match ctype {
+            0 => { rgb.extend([px[0]; 3]); alpha.push(255); }
+            4 => { rgb.extend([px[0]; 3]); alpha.push(px[1]); }
+            2 => { rgb.extend(&px[..3]); alpha.push(255); }
+            6 => { rgb.extend(&px[..3]); alpha.push(px[3]); }
+            _ => {
+                let idx = px[0] as usize;
+                rgb.extend(palette.get(idx * 3..idx * 3 + 3)?);
+                alpha.push(trns.get(idx).copied().unwrap_or(255));
+            }
+        }
+    }
+    let alpha = if alpha.iter().all(|a| *a == 255) { None } else { Some(alpha) };
+    Some(Image { width, height, data: ImageData::Raw { rgb, alpha } })
+}
+
+// Reverses the per-row PNG filters (None, Sub, Up, Average, Paeth)
+fn unfilter(raw: &[u8], stride: usize, rows: usize, bpp: usize) -> Option<Vec<u8>> {
+    if raw.len() < (stride + 1) * rows { return None; }
+    let mut out = vec![0u8; stride * rows];
+    for y in 0..rows {
+        let filter = raw[y * (stride + 1)];
+        let src = &raw[y * (stride + 1) + 1..(y + 1) * (stride + 1)];
+        for x in 0..stride {
+            let a = if x >= bpp { out[y * stride + x - bpp] } else { 0 };
+            let b = if y > 0 { out[(y - 1) * stride + x] } else { 0 };
+            let c = if x >= bpp && y > 0 { out[(y - 1) * stride + x - bpp] } else { 0 };
+            let pred = match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return None,
+            };
+            out[y * stride + x] = src[x].wrapping_add(pred);
+        }
+    }
+    Some(out)
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = ((p - a as i16).abs(), (p - b as i16).abs(), (p - c as i16).abs());
+    if pa <= pb && pa <= pc { a } else if pb <= pc { b } else { c }
+}
+
+/// Solid-color RGB PNG, used for palette swatches in Markdown and DOCX exports.
+pub fn swatch_png((r, g, b): crate::palette::color::Rgb, width: u32, height: u32) -> Vec<u8> {
+    let row: Vec<u8> = std::iter::once(0).chain((0..width).flat_map(|_| [r, g, b])).collect();
+    let raw = row.repeat(height as usize);
+    let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+    let _ = std::io::Write::write_all(&mut enc, &raw);
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend(width.to_be_bytes());
+    ihdr.extend(height.to_be_bytes());
+    ihdr.extend([8, 2, 0, 0, 0]);
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    for (kind, data) in [(b"IHDR", ihdr), (b"IDAT", enc.finish().unwrap_or_default()), (b"IEND", Vec::new())] {
+        out.extend((data.len() as u32).to_be_bytes());
+        let mut crc = flate2::Crc::new();
+        crc.update(kind);
+        crc.update(&data);
+        out.extend_from_slice(kind);
+        out.extend(data);
+        out.extend(crc.sum().to_be_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // PNG with the given IHDR payload and raw (pre-compression) scanlines
+    fn png(ihdr: &[u8], raw: &[u8]) -> Vec<u8> {
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::fast());
+        std::io::Write::write_all(&mut enc, raw).unwrap();
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        for (kind, data) in [(b"IHDR", ihdr.to_vec()), (b"IDAT", enc.finish().unwrap()), (b"IEND", Vec::new())] {
+            out.extend((data.len() as u32).to_be_bytes());
+            out.extend_from_slice(kind);
+            out.extend(data);
+            out.extend([0; 4]);
+        }
+        out
+    }
+
+    #[test]
+    fn rejects_malformed_and_oversized_pngs() {
+        let swatch = decode(&swatch_png((10, 20, 30), 2, 2)).unwrap();
+        assert_eq!((swatch.width, swatch.height), (2, 2));
+        // Truncated IHDR
+        assert!(decode(&png(&[0, 0, 0, 2, 0, 0, 0, 2, 8], &[])).is_none());
+        // 1×1 RGB header, but IDAT inflates far past one row
+        let ihdr = [0, 0, 0, 1, 0, 0, 0, 1, 8, 2, 0, 0, 0];
+        assert!(decode(&png(&ihdr, &[0, 1, 2, 3])).is_some());
+        assert!(decode(&png(&ihdr, &vec![0; 1 << 20])).is_none());
+    }
+
+    #[test]
+    fn only_public_addresses_are_fetched() {
+        for ip in ["169.254.169.254", "127.0.0.1", "10.1.2.3", "192.168.0.1", "100.64.0.1", "0.0.0.0", "::1", "fe80::1", "fd00::1", "::ffff:127.0.0.1"] {
+            assert!(!is_public(ip.parse().unwrap()), "{}", ip);
+        }
+        assert!(is_public("93.184.216.34".parse().unwrap()));
+        assert!(is_public("2606:4700::1111".parse().unwrap()));
+    }
+}
@@ -0,0 +1,77 @@
+// GitHub-flavored Markdown rendering of the guide outline. Swatches are inline PNG data URLs so the
+// file is self-contained; output is byte-for-byte stable for the same guide.
+use base64::Engine;
+
+use super::image::swatch_png;
+use super::outline::{outline, Block, Cell};
+use crate::models::BrandGuide;
+
+pub fn render(guide: &BrandGuide) -> String {
+    let mut out = String::new();
+    for block in outline(guide) {
+        match block {
+            Block::Title(t) => out.push_str(&format!("# {}\n\n", inline(&t))),
+            Block::Subtitle(t) => out.push_str(&format!("_{}_\n\n", inline(&t))),
+            Block::Heading(t) => out.push_str(&format!("## {}\n\n", inline(&t))),
+            Block::Paragraph(t) => out.push_str(&format!("{}\n\n", inline(&t))),
+            Block::Strong(t) => out.push_str(&format!("**{}**\n\n", inline(&t))),
+            Block::Quote(t) => out.push_str(&format!("> {}\n\n", inline(&t))),
+            Block::Bullets(items) => {
+                for i in items { out.push_str(&format!("- {}\n", inline(&i))); }
+                out.push('\n');
+            }
+            Block::Table { headers, rows } => {
+                if rows.is_empty() { out.push_str("_None yet._\n\n"); continue; }
+                out.push_str(&format!("| {} |\n", headers.iter().map(|h| cell_text(h)).collect::<Vec<_>>().join(" | ")));
+                out.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+                for r in rows { out.push_str(&format!("| {} |\n", r.iter().map(cell).collect::<Vec<_>>().join(" | "))); }
+                out.push('\n');
+            }
+        }
+    }
+    format!("{}\n", out.trim_end())
+}
+
+fn cell(c: &Cell) -> String {
+    match c {
+        Cell::Text(s) => cell_text(s),
+        Cell::Swatch(rgb) => {
+            let png = base64::engine::general_purpose::STANDARD.encode(swatch_png(*rgb, 32, 16));
+            format!("![{}](data:image/png;base64,{})", c.text(), png)
+        }
+    }
+}
+
+// Table cells are single-line and `|` would end the cell
+fn cell_text(s: &str) -> String {
+    inline(s).replace('|', "\\|").replace('\n', "<br>")
+}
+
+// Collapses blank lines so free text can't break out of its block, escapes `<` so it isn't read as
+// HTML, and escapes leading Markdown markers
+fn inline(s: &str) -> String {
+    let joined = s.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).collect::<Vec<_>>().join("\n").replace('<', "\\<");
+    match joined.chars().next() {
+        Some('#' | '>' | '-' | '+' | '*' | '|') => format!("\\{}", joined),
+        _ => joined,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_escape_pipes_and_embed_swatches() {
+        let guide: BrandGuide = serde_json::from_value(serde_json::json!({
+            "brandName": "Acme", "industry": "Tools", "mission": "# Not a heading", "audience": "Makers.", "elevatorPitch": "Pitch.",
+            "tone": {"traits": [], "description": "Plain.", "dosAndDonts": {"dos": ["Say a | b"], "donts": ["Hype"]}},
+            "taglines": [], "palette": {"primary": "#000000"}
+        })).unwrap();
+        let md = render(&guide);
+        assert!(md.starts_with("# Acme brand guidelines\n\n_Tools_\n\n## Mission\n\n\\# Not a heading\n"));
+        assert!(md.contains("| Say a \\| b | Hype |\n"));
+        assert!(md.contains("| ![#000000](data:image/png;base64,iVBORw0KGgo"));
+        assert!(md.contains("## Taglines\n\n_None yet._\n"));
+    }
+}
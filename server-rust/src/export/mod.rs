@@ -0,0 +1,9 @@
+pub mod fonts;
+pub mod image;
+pub mod pdf;
+pub mod brandbook;
+pub mod outline;
+pub mod markdown;
+pub mod docx;
+pub mod zip;
+pub mod tokens;
@@ -0,0 +1,114 @@
+// Format-neutral outline of a guide shared by the Markdown and DOCX exporters, so both list the
+// same sections in the same order. Everything is derived from the guide alone (deterministic output).
+use crate::models::{BrandGuide, Palette};
+use crate::palette::color::{self, Rgb};
+
+// Roles listed first, in this order; any others follow alphabetically
+const ROLE_ORDER: &[&str] = &["primary", "secondary", "accent", "background", "text", "link", "neutralLight", "neutralDark"];
+const WHITE: Rgb = (255, 255, 255);
+const BLACK: Rgb = (0, 0, 0);
+
+pub enum Block {
+    Title(String),
+    Subtitle(String),
+    Heading(String),
+    Paragraph(String),
+    Strong(String),
+    Quote(String),
+    Bullets(Vec<String>),
+    Table { headers: Vec<String>, rows: Vec<Vec<Cell>> },
+}
+
+pub enum Cell { Text(String), Swatch(Rgb) }
+
+impl Cell {
+    pub fn text(&self) -> String {
+        match self { Cell::Text(s) => s.clone(), Cell::Swatch(rgb) => color::to_hex(*rgb) }
+    }
+}
+
+/// Parsable palette roles in display order; invalid hex values are skipped.
+pub fn ordered_palette(palette: &Palette) -> Vec<(String, Rgb)> {
+    let mut roles: Vec<(String, Rgb)> = palette.iter().filter_map(|(k, v)| Some((k.clone(), color::parse_color(v)?))).collect();
+    let rank = |k: &str| ROLE_ORDER.iter().position(|r| *r == k).unwrap_or(ROLE_ORDER.len());
+    roles.sort_by(|a, b| rank(&a.0).cmp(&rank(&b.0)).then(a.0.cmp(&b.0)));
+    roles
+}
+
+pub fn outline(guide: &BrandGuide) -> Vec<Block> {
+    let text = |s: &str| Cell::Text(s.to_string());
+    let mut out = vec![Block::Title(format!("{} brand guidelines", guide.brandName)), Block::Subtitle(guide.industry.clone())];
+    if let Some(t) = guide.taglines.first() { out.push(Block::Quote(t.tagline.clone())); }
+
+    out.push(Block::Heading("Mission".into()));
+    out.push(Block::Paragraph(guide.mission.clone()));
+    out.push(Block::Heading("Audience".into()));
+    out.push(Block::Paragraph(guide.audience.clone()));
+
+    out.push(Block::Heading("Tone of voice".into()));
+    if !guide.tone.traits.is_empty() { out.push(Block::Strong(guide.tone.traits.join(" · "))); }
+    out.push(Block::Paragraph(guide.tone.description.clone()));
+    let dd = &guide.tone.dosAndDonts;
+    let rows = (0..dd.dos.len().max(dd.donts.len()))
+        .map(|i| vec![text(dd.dos.get(i).map(|s| s.as_str()).unwrap_or("")), text(dd.donts.get(i).map(|s| s.as_str()).unwrap_or(""))])
+        .collect();
+    out.push(Block::Table { headers: vec!["Do".into(), "Don't".into()], rows });
+
+    out.push(Block::Heading("Taglines".into()));
+    let rows = guide.taglines.iter().map(|t| vec![text(&t.tagline), text(&t.rationale)]).collect();
+    out.push(Block::Table { headers: vec!["Tagline".into(), "Rationale".into()], rows });
+
+    out.push(Block::Heading("Elevator pitch".into()));
+    out.push(Block::Paragraph(guide.elevatorPitch.clone()));
+
+    out.push(Block::Heading("Color palette".into()));
+    out.push(palette_table(&guide.palette));
+
+    out.push(Block::Heading("Font pairings".into()));
+    if guide.fontPairings.is_empty() {
+        out.push(Block::Paragraph("No font pairings defined yet.".into()));
+    } else {
+        let rows = guide.fontPairings.iter().map(|p| vec![text(p.name.as_deref().unwrap_or("")), text(&p.heading), text(&p.body)]).collect();
+        out.push(Block::Table { headers: vec!["Name".into(), "Heading".into(), "Body".into()], rows });
+    }
+
+    if !guide.bannedTerms.is_empty() || !guide.preferredTerms.is_empty() {
+        out.push(Block::Heading("Vocabulary".into()));
+        if !guide.bannedTerms.is_empty() { out.push(Block::Strong("Never use".into())); out.push(Block::Bullets(guide.bannedTerms.clone())); }
+        if !guide.preferredTerms.is_empty() {
+            let rows = guide.preferredTerms.iter().map(|t| vec![text(&t.avoid), text(&t.prefer)]).collect();
+            out.push(Block::Table { headers: vec!["Instead of".into(), "Say".into()], rows });
+        }
+    }
+
+    for l in &guide.locales {
+        out.push(Block::Heading(format!("Locale: {}", l.locale)));
+        if let Some(n) = l.toneNotes.as_deref().filter(|n| !n.trim().is_empty()) { out.push(Block::Paragraph(n.to_string())); }
+        if !l.bannedTerms.is_empty() { out.push(Block::Strong("Never use".into())); out.push(Block::Bullets(l.bannedTerms.clone())); }
+        if !l.preferredTerms.is_empty() {
+            let rows = l.preferredTerms.iter().map(|t| vec![text(&t.avoid), text(&t.prefer)]).collect();
+            out.push(Block::Table { headers: vec!["Instead of".into(), "Say".into()], rows });
+        }
+    }
+    out
+}
+
+fn palette_table(palette: &Palette) -> Block {
+    let background = palette.get("background").and_then(|h| color::parse_color(h));
+    let rate = |a: Rgb, b: Rgb| { let r = color::contrast_ratio(a, b); format!("{:.1}:1 {}", r, color::wcag_rating(r)) };
+    let rows = ordered_palette(palette).into_iter().map(|(role, rgb)| {
+        let (h, s, l) = color::rgb_to_hsl(rgb);
+        vec![
+            Cell::Swatch(rgb),
+            Cell::Text(role.clone()),
+            Cell::Text(color::to_hex(rgb)),
+            Cell::Text(format!("{}, {}, {}", rgb.0, rgb.1, rgb.2)),
+            Cell::Text(format!("{:.0}°, {:.0}%, {:.0}%", h, s, l)),
+            Cell::Text(rate(rgb, WHITE)),
+            Cell::Text(rate(rgb, BLACK)),
+            Cell::Text(match background { Some(bg) if role != "background" => rate(rgb, bg), _ => "—".into() }),
+        ]
+    }).collect();
+    let headers = ["Swatch", "Role", "Hex", "RGB", "HSL", "White text", "Black text", "On background"];
+    Block::Table { headers: headers.iter().map(|h| h.to_string()).collect(), rows }
+}
@@ -0,0 +1,163 @@
+// Minimal PDF 1.4 writer: base-14 fonts, filled/stroked rectangles, lines, images and a flowing
+// text cursor with automatic page breaks. Enough for generated documents; no external renderer.
+use std::io::Write;
+
+use super::fonts::{self, Font};
+use super::image::{Image, ImageData};
+use crate::palette::color::Rgb;
+
+pub const PAGE_W: f32 = 612.0; // US Letter
+pub const PAGE_H: f32 = 792.0;
+pub const MARGIN: f32 = 54.0;
+const FOOTER_SPACE: f32 = 28.0;
+
+pub struct Pdf {
+    pages: Vec<Vec<u8>>,
+    cur: Vec<u8>,
+    images: Vec<Image>,
+    title: String,
+    // Baseline cursor, measured from the bottom of the page
+    pub y: f32,
+}
+
+impl Pdf {
+    pub fn new(title: &str) -> Pdf {
+        Pdf { pages: Vec::new(), cur: Vec::new(), images: Vec::new(), title: title.to_string(), y: PAGE_H - MARGIN }
+    }
+
+    pub fn content_width(&self) -> f32 { PAGE_W - 2.0 * MARGIN }
+
+    pub fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.cur));
+        self.y = PAGE_H - MARGIN;
+    }
+
+    /// Starts a new page unless `h` points still fit above the footer.
+    pub fn ensure(&mut self, h: f32) {
+        if self.y - h < MARGIN + FOOTER_SPACE { self.new_page(); }
+    }
+
+    pub fn text(&mut self, x: f32, y: f32, font: Font, size: f32, color: Rgb, s: &str) {
+        let _ = write!(self.cur, "BT {} /{} {:.1} Tf {:.2} {:.2} Td ", rg(color), font.resource(), size, x, y);
+        self.cur.extend(fonts::pdf_string(s));
+        self.cur.extend_from_slice(b" Tj ET\n");
+    }
+
+    /// Wrapped text at `x` within `width`, advancing the cursor and breaking pages between lines.
+    pub fn paragraph(&mut self, x: f32, width: f32, font: Font, size: f32, color: Rgb, s: &str) {
+        let leading = size * 1.4;
+        for line in fonts::wrap(font, size, s, width) {
+            self.ensure(leading);
+            self.y -= leading;
+            self.text(x, self.y, font, size, color, &line);
+        }
+    }
+
+    pub fn rect(&mut self, x: f32, y: f32, w: f32, h: f32, fill: Option<Rgb>, stroke: Option<Rgb>) {
+        let op = match (fill, stroke) { (Some(_), Some(_)) => "B", (Some(_), None) => "f", (None, Some(_)) => "S", (None, None) => return };
+        let _ = write!(self.cur, "q ");
+        if let Some(c) = fill { let _ = write!(self.cur, "{} ", rg(c)); }
+        if let Some(c) = stroke { let _ = write!(self.cur, "{} 0.5 w ", rg(c).to_uppercase()); }
+        let _ = writeln!(self.cur, "{:.2} {:.2} {:.2} {:.2} re {} Q", x, y, w, h, op);
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: Rgb, width: f32) {
+        let _ = writeln!(self.cur, "q {} {:.2} w {:.2} {:.2} m {:.2} {:.2} l S Q", rg(color).to_uppercase(), width, x1, y1, x2, y2);
+    }
+
+    pub fn image(&mut self, img: Image, x: f32, y: f32, w: f32, h: f32) {
+        self.images.push(img);
+        let _ = writeln!(self.cur, "q {:.2} 0 0 {:.2} {:.2} {:.2} cm /Im{} Do Q", w, h, x, y, self.images.len());
+    }
+
+    /// Serializes the document; `footer(page, total)` is drawn at the bottom of every page.
+    pub fn finish(mut self, footer: impl Fn(usize, usize) -> String) -> Vec<u8> {
+        if !self.cur.is_empty() || self.pages.is_empty() { self.pages.push(std::mem::take(&mut self.cur)); }
+        let total = self.pages.len();
+        let mut objs: Vec<Vec<u8>> = Vec::new();
+        // 1 catalog, 2 page tree, 3 info, then fonts; page tree is filled in once page ids are known
+        objs.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objs.push(Vec::new());
+        let mut info = b"<< /Producer (brand_voice_ai_server) /Title ".to_vec();
+        info.extend(fonts::pdf_string(&self.title));
+        info.extend_from_slice(b" >>");
+        objs.push(info);
+        let mut font_refs = String::new();
+        for (f, base) in Font::ALL {
+            objs.push(format!("<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>", base).into_bytes());
+            font_refs.push_str(&format!("/{} {} 0 R ", f.resource(), objs.len()));
+        }
+        let mut xobj_refs = String::new();
+        for (i, img) in std::mem::take(&mut self.images).into_iter().enumerate() {
+            let id = image_objects(&mut objs, img);
+            xobj_refs.push_str(&format!("/Im{} {} 0 R ", i + 1, id));
+        }
+        let resources = format!("<< /Font << {}>> /XObject << {}>> >>", font_refs, xobj_refs);
+        let mut kids = Vec::new();
+        for (i, mut content) in std::mem::take(&mut self.pages).into_iter().enumerate() {
+            let label = footer(i + 1, total);
+            let _ = write!(content, "BT {} /F1 8.0 Tf {:.2} {:.2} Td ", rg((120, 120, 120)), MARGIN, MARGIN - 20.0);
+            content.extend(fonts::pdf_string(&label));
+            content.extend_from_slice(b" Tj ET\n");
+            objs.push(stream(b"", &deflate(&content), true));
+            let content_id = objs.len();
+            objs.push(format!("<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources {} /Contents {} 0 R >>", PAGE_W, PAGE_H, resources, content_id).into_bytes());
+            kids.push(format!("{} 0 R", objs.len()));
+        }
+        objs[1] = format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), total).into_bytes();
+
+        let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objs.len());
+        for (i, body) in objs.iter().enumerate() {
+            offsets.push(out.len());
+            let _ = writeln!(out, "{} 0 obj", i + 1);
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+        let xref = out.len();
+        let _ = write!(out, "xref\n0 {}\n0000000000 65535 f \n", objs.len() + 1);
+        for o in offsets { let _ = writeln!(out, "{:010} 00000 n ", o); }
+        let _ = write!(out, "trailer\n<< /Size {} /Root 1 0 R /Info 3 0 R >>\nstartxref\n{}\n%%EOF\n", objs.len() + 1, xref);
+        out
+    }
+}
+
+fn rg((r, g, b): Rgb) -> String {
+    format!("{:.3} {:.3} {:.3} rg", r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    let _ = enc.write_all(data);
+    enc.finish().unwrap_or_default()
+}
+
+fn stream(dict_extra: &[u8], data: &[u8], flate: bool) -> Vec<u8> {
+    let mut out = format!("<< /Length {} ", data.len()).into_bytes();
+    if flate { out.extend_from_slice(b"/Filter /FlateDecode "); }
+    out.extend_from_slice(dict_extra);
+    out.extend_from_slice(b">>\nstream\n");
+    out.extend_from_slice(data);
+    out.extend_from_slice(b"\nendstream");
+    out
+}
+
+// Pushes the image (and its soft mask) and returns the image object id
+fn image_objects(objs: &mut Vec<Vec<u8>>, img: Image) -> usize {
+    let head = format!("/Type /XObject /Subtype /Image /Width {} /Height {} /BitsPerComponent 8 ", img.width, img.height);
+    match img.data {
+        ImageData::Jpeg { data, components } => {
+            let cs = if components == 1 { "/DeviceGray" } else { "/DeviceRGB" };
+            objs.push(stream(format!("{}/ColorSpace {} /Filter /DCTDecode ", head, cs).as_bytes(), &data, false));
+        }
+        ImageData::Raw { rgb, alpha } => {
+            let mut extra = format!("{}/ColorSpace /DeviceRGB ", head);
+            if let Some(a) = alpha {
+                objs.push(stream(format!("{}/ColorSpace /DeviceGray ", head).as_bytes(), &deflate(&a), true));
+                extra.push_str(&format!("/SMask {} 0 R ", objs.len()));
+            }
+            objs.push(stream(extra.as_bytes(), &deflate(&rgb), true));
+        }
+    }
+    objs.len()
+}
@@ -0,0 +1,222 @@
+// Design token export of palette roles. `*Dark` roles become the dark-mode value of their base role
+// (backgroundDark → background); a dark role without a base is exported as its own token.
+use serde_json::{json, Map, Value};
+
+use crate::models::Palette;
+use crate::palette::color::{self, Rgb};
+use crate::palette::scales;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format { Dtcg, StyleDictionary, Css, Tailwind, Scss, Android, AndroidNight, Ios }
+
+impl Format {
+    pub const NAMES: &'static [&'static str] = &["dtcg", "style-dictionary", "css", "tailwind", "scss", "android", "android-night", "ios"];
+
+    pub fn parse(s: &str) -> Option<Format> {
+        Some(match s.trim().to_ascii_lowercase().as_str() {
+            "dtcg" | "w3c" | "design-tokens" => Format::Dtcg,
+            "style-dictionary" | "styledictionary" | "sd" => Format::StyleDictionary,
+            "css" => Format::Css,
+            "tailwind" => Format::Tailwind,
+            "scss" | "sass" => Format::Scss,
+            "android" => Format::Android,
+            "android-night" => Format::AndroidNight,
+            "ios" | "xcassets" => Format::Ios,
+            _ => return None,
+        })
+    }
+
+    /// (content type, download file name)
+    pub fn file(self) -> (&'static str, &'static str) {
+        match self {
+            Format::Dtcg => ("application/json", "tokens.json"),
+            Format::StyleDictionary => ("application/json", "color.tokens.json"),
+            Format::Css => ("text/css", "tokens.css"),
+            Format::Tailwind => ("application/javascript", "tailwind.config.js"),
+            Format::Scss => ("text/x-scss", "_tokens.scss"),
+            Format::Android => ("application/xml", "colors.xml"),
+            Format::AndroidNight => ("application/xml", "colors-night.xml"),
+            Format::Ios => ("application/json", "Colors.xcassets.json"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColorToken { pub name: String, pub value: Rgb, pub dark: Option<Rgb> }
+
+/// Palette roles as tokens, sorted by name; values that aren't valid hex are skipped.
+pub fn from_palette(palette: &Palette) -> Vec<ColorToken> {
+    let mut out: Vec<ColorToken> = Vec::new();
+    for (role, hex) in palette {
+        let Some(value) = color::parse_color(hex) else { continue };
+        if let Some(base) = role.strip_suffix("Dark") {
+            if palette.get(base).and_then(|h| color::parse_color(h)).is_some() { continue; }
+        }
+        let dark = palette.get(&format!("{}Dark", role)).and_then(|h| color::parse_color(h));
+        out.push(ColorToken { name: role.clone(), value, dark });
+    }
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    out
+}
+
+/// Adds `role-50` … `role-950` tonal steps after each scaled role (palette::scales::ROLES); the dark value
+/// of a step is the same step of the role's dark color, when it has one.
+pub fn with_scales(tokens: Vec<ColorToken>) -> Vec<ColorToken> {
+    let mut out = Vec::new();
+    for t in tokens {
+        let steps = if scales::ROLES.contains(&t.name.as_str()) {
+            let light = scales::ramp(t.value).1;
+            let dark = t.dark.map(|d| scales::ramp(d).1);
+            light.into_iter().enumerate()
+                .map(|(i, (step, value))| ColorToken { name: format!("{}-{}", t.name, step), value, dark: dark.as_ref().map(|d| d[i].1) })
+                .collect()
+        } else { vec![] };
+        out.push(t);
+        out.extend(steps);
+    }
+    out
+}
+
+pub fn render(tokens: &[ColorToken], format: Format) -> String {
+    match format {
+        Format::Dtcg => pretty(&groups(tokens, |v| json!({"$type": "color", "$value": color::to_hex(v)}))),
+        Format::StyleDictionary => pretty(&groups(tokens, |v| json!({"value": color::to_hex(v), "type": "color"}))),
+        Format::Css => css(tokens),
+        Format::Tailwind => tailwind(tokens),
+        Format::Scss => scss(tokens),
+        Format::Android => android(tokens, false),
+        Format::AndroidNight => android(tokens, true),
+        Format::Ios => pretty(&ios(tokens)),
+    }
+}
+
+fn pretty(v: &Value) -> String { serde_json::to_string_pretty(v).unwrap_or_default() }
+
+// {"color": {role: leaf, "dark": {role: leaf}}}
+fn groups(tokens: &[ColorToken], leaf: impl Fn(Rgb) -> Value) -> Value {
+    let mut color = Map::new();
+    let mut dark = Map::new();
+    for t in tokens {
+        color.insert(kebab(&t.name), leaf(t.value));
+        if let Some(d) = t.dark { dark.insert(kebab(&t.name), leaf(d)); }
+    }
+    if !dark.is_empty() { color.insert("dark".into(), Value::Object(dark)); }
+    json!({"color": color})
+}
+
+fn css(tokens: &[ColorToken]) -> String {
+    let mut out = String::from(":root {\n");
+    for t in tokens { out.push_str(&format!("  --color-{}: {};\n", kebab(&t.name), color::to_hex(t.value))); }
+    out.push_str("}\n");
+    if tokens.iter().any(|t| t.dark.is_some()) {
+        out.push_str("\n@media (prefers-color-scheme: dark) {\n  :root {\n");
+        for t in tokens {
+            if let Some(d) = t.dark { out.push_str(&format!("    --color-{}: {};\n", kebab(&t.name), color::to_hex(d))); }
+        }
+        out.push_str("  }\n}\n");
+    }
+    out
+}
+
+fn tailwind(tokens: &[ColorToken]) -> String {
+    let mut out = String::from("/** @type {import('tailwindcss').Config} */\nmodule.exports = {\n  theme: {\n    extend: {\n      colors: {\n");
+    for t in tokens {
+        let name = kebab(&t.name);
+        match t.dark {
+            // `bg-background dark:bg-background-dark`
+            Some(d) => out.push_str(&format!("        '{}': {{ DEFAULT: '{}', dark: '{}' }},\n", name, color::to_hex(t.value), color::to_hex(d))),
+            None => out.push_str(&format!("        '{}': '{}',\n", name, color::to_hex(t.value))),
+        }
+    }
+    out.push_str("      },\n    },\n  },\n};\n");
+    out
+}
+
+fn scss(tokens: &[ColorToken]) -> String {
+    let mut out = String::new();
+    let mut map = Vec::new();
+    for t in tokens {
+        let name = kebab(&t.name);
+        out.push_str(&format!("$color-{}: {};\n", name, color::to_hex(t.value)));
+        map.push(format!("  '{}': $color-{}", name, name));
+        if let Some(d) = t.dark {
+            out.push_str(&format!("$color-{}-dark: {};\n", name, color::to_hex(d)));
+            map.push(format!("  '{}-dark': $color-{}-dark", name, name));
+        }
+    }
+    out.push_str(&format!("\n$colors: (\n{}\n);\n", map.join(",\n")));
+    out
+}
+
+// values/colors.xml, or values-night/colors.xml with the dark values under the same names
+fn android(tokens: &[ColorToken], night: bool) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+    for t in tokens {
+        let v = if night { t.dark.unwrap_or(t.value) } else { t.value };
+        out.push_str(&format!("    <color name=\"{}\">#FF{}</color>\n", snake(&t.name), &color::to_hex(v)[1..]));
+    }
+    out.push_str("</resources>\n");
+    out
+}
+
+// Path → Contents.json for each colorset, with a dark luminosity appearance when available
+fn ios(tokens: &[ColorToken]) -> Value {
+    let comps = |(r, g, b): Rgb| json!({"color-space": "srgb", "components": {"red": format!("0x{:02X}", r), "green": format!("0x{:02X}", g), "blue": format!("0x{:02X}", b), "alpha": "1.000"}});
+    let mut files = Map::new();
+    files.insert("Colors.xcassets/Contents.json".into(), json!({"info": {"author": "xcode", "version": 1}}));
+    for t in tokens {
+        let mut colors = vec![json!({"idiom": "universal", "color": comps(t.value)})];
+        if let Some(d) = t.dark {
+            colors.push(json!({"idiom": "universal", "appearances": [{"appearance": "luminosity", "value": "dark"}], "color": comps(d)}));
+        }
+        files.insert(format!("Colors.xcassets/{}.colorset/Contents.json", pascal(&t.name)), json!({"colors": colors, "info": {"author": "xcode", "version": 1}}));
+    }
+    Value::Object(files)
+}
+
+// camelCase role → words
+fn words(name: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for c in name.chars() {
+        if !c.is_alphanumeric() { out.push(String::new()); continue; }
+        if c.is_uppercase() || out.is_empty() { out.push(String::new()); }
+        if let Some(w) = out.last_mut() { w.extend(c.to_lowercase()); }
+    }
+    out.into_iter().filter(|w| !w.is_empty()).collect()
+}
+
+pub fn kebab(name: &str) -> String { words(name).join("-") }
+
+fn snake(name: &str) -> String { words(name).join("_") }
+
+fn pascal(name: &str) -> String {
+    words(name).iter().map(|w| { let mut c = w.chars(); c.next().map(|f| f.to_uppercase().chain(c).collect::<String>()).unwrap_or_default() }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dark_roles_pair_with_their_base() {
+        let palette: Palette = [("background", "#ffffff"), ("backgroundDark", "#111"), ("neutralLight", "#EEEEEE"), ("accentDark", "#222222")]
+            .iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let tokens = from_palette(&palette);
+        assert_eq!(tokens.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["accentDark", "background", "neutralLight"]);
+        let css = render(&tokens, Format::Css);
+        assert!(css.contains("--color-neutral-light: #EEEEEE;"));
+        assert!(css.contains("@media (prefers-color-scheme: dark) {\n  :root {\n    --color-background: #111111;"));
+        assert!(render(&tokens, Format::AndroidNight).contains("<color name=\"background\">#FF111111</color>"));
+    }
+
+    #[test]
+    fn scales_follow_their_role() {
+        let palette: Palette = [("primary", "#2563EB"), ("background", "#FFFFFF")].iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let scaled = with_scales(from_palette(&palette));
+        assert_eq!(scaled.len(), 2 + scales::STEPS.len());
+        assert_eq!((scaled[1].name.as_str(), scaled[2].name.as_str()), ("primary", "primary-50"));
+        let css = render(&scaled, Format::Css);
+        assert!(css.contains("--color-primary-950: ") && css.contains("--color-primary-600: #2563EB;"), "{}", css);
+        assert!(render(&scaled, Format::Android).contains("<color name=\"primary_50\">"));
+    }
+}
@@ -0,0 +1,86 @@
+// Minimal ZIP support for OOXML packages: a deterministic writer (deflate, fixed 1980-01-01
+// timestamps) and a reader for single entries of uploaded files.
+use std::io::{Read, Write};
+
+#[derive(Default)]
+pub struct ZipWriter { out: Vec<u8>, central: Vec<u8>, count: u16 }
+
+impl ZipWriter {
+    pub fn add(&mut self, name: &str, data: &[u8]) {
+        let mut crc = flate2::Crc::new();
+        crc.update(data);
+        let mut enc = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        let _ = enc.write_all(data);
+        let packed = enc.finish().unwrap_or_default();
+        let offset = self.out.len() as u32;
+        // version, flags, method 8 (deflate), time 00:00, date 1980-01-01, crc, sizes, name length
+        let common = |buf: &mut Vec<u8>| {
+            buf.extend(20u16.to_le_bytes());
+            buf.extend(0u16.to_le_bytes());
+            buf.extend(8u16.to_le_bytes());
+            buf.extend(0u16.to_le_bytes());
+            buf.extend(0x21u16.to_le_bytes());
+            buf.extend(crc.sum().to_le_bytes());
+            buf.extend((packed.len() as u32).to_le_bytes());
+            buf.extend((data.len() as u32).to_le_bytes());
+            buf.extend((name.len() as u16).to_le_bytes());
+            buf.extend(0u16.to_le_bytes());
+        };
+        self.out.extend(0x04034b50u32.to_le_bytes());
+        common(&mut self.out);
+        self.out.extend_from_slice(name.as_bytes());
+        self.out.extend(&packed);
+
+        self.central.extend(0x02014b50u32.to_le_bytes());
+        self.central.extend(20u16.to_le_bytes());
+        common(&mut self.central);
+        // comment length, disk, internal attrs, external attrs, local header offset
+        self.central.extend([0u8; 10]);
+        self.central.extend(offset.to_le_bytes());
+        self.central.extend_from_slice(name.as_bytes());
+        self.count += 1;
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        let (cd_offset, cd_len) = (self.out.len() as u32, self.central.len() as u32);
+        self.out.append(&mut self.central);
+        self.out.extend(0x06054b50u32.to_le_bytes());
+        self.out.extend([0u8; 4]);
+        self.out.extend(self.count.to_le_bytes());
+        self.out.extend(self.count.to_le_bytes());
+        self.out.extend(cd_len.to_le_bytes());
+        self.out.extend(cd_offset.to_le_bytes());
+        self.out.extend(0u16.to_le_bytes());
+        self.out
+    }
+}
+
+/// Inflated contents of the entry named `name`; None when missing, encrypted or not stored/deflated.
+pub fn read_entry(zip: &[u8], name: &str) -> Option<Vec<u8>> {
+    let u16_at = |i: usize| zip.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize);
+    let u32_at = |i: usize| zip.get(i..i + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+    // End of central directory record, searched from the end past any trailing comment
+    let eocd = (0..zip.len().saturating_sub(21)).rev().find(|&i| zip[i..].starts_with(&0x06054b50u32.to_le_bytes()))?;
+    let (count, mut at) = (u16_at(eocd + 10)?, u32_at(eocd + 16)?);
+    for _ in 0..count {
+        if u32_at(at)? != 0x02014b50 { return None; }
+        let (flags, method, packed, size) = (u16_at(at + 8)?, u16_at(at + 10)?, u32_at(at + 20)?, u32_at(at + 24)?);
+        let (name_len, extra_len, comment_len, local) = (u16_at(at + 28)?, u16_at(at + 30)?, u16_at(at + 32)?, u32_at(at + 42)?);
+        if zip.get(at + 46..at + 46 + name_len)? == name.as_bytes() {
+            if flags & 1 != 0 { return None; }
+            let start = local + 30 + u16_at(local + 26)? + u16_at(local + 28)?;
+            let data = zip.get(start..start + packed)?;
+            return match method {
+                0 => Some(data.to_vec()),
+                8 => {
+                    let mut out = Vec::with_capacity(size.min(64 << 20));
+                    flate2::read::DeflateDecoder::new(data).take(64 << 20).read_to_end(&mut out).ok()?;
+                    Some(out)
+                }
+                _ => None,
+            };
+        }
+        at += 46 + name_len + extra_len + comment_len;
+    }
+    None
+}
@@ -0,0 +1,189 @@
+// Rewrite ids and user feedback. Each rewrite is remembered briefly so feedback can be tied to its
+// settings (aggressiveness, model, channel); feedback is kept per guide and summarized as acceptance stats.
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::models::RewriteRequest;
+
+// Rewrites older than this many are forgotten (feedback on them returns 404)
+const REWRITE_CAPACITY: usize = 4096;
+// Feedback entries kept per guide
+const FEEDBACK_PER_GUIDE: usize = 2000;
+// Guides with feedback kept; least recently used beyond this are dropped (same as the guide store)
+const GUIDE_CAPACITY: usize = 1024;
+// Same default as build_rewrite_system
+const DEFAULT_AGGRESSIVENESS: i32 = 2;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct RewriteRecord {
+    pub guideId: Option<String>,
+    pub original: String,
+    pub rewritten: String,
+    pub aggressiveness: i32,
+    pub model: String,
+    pub channel: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict { Accept, Reject, Edit }
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedbackRequest { pub verdict: Verdict, pub editedText: Option<String>, pub comment: Option<String> }
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedbackEntry {
+    pub rewriteId: String,
+    pub verdict: Verdict,
+    pub aggressiveness: i32,
+    pub model: String,
+    pub channel: Option<String>,
+    pub comment: Option<String>,
+}
+
+pub struct Feedback {
+    rewrites: lru::LruCache<String, RewriteRecord>,
+    by_guide: lru::LruCache<String, Vec<FeedbackEntry>>,
+}
+
+pub type FeedbackStore = Arc<tokio::sync::Mutex<Feedback>>;
+
+pub fn new_store() -> FeedbackStore {
+    Arc::new(tokio::sync::Mutex::new(Feedback {
+        rewrites: lru::LruCache::new(NonZeroUsize::new(REWRITE_CAPACITY).unwrap()),
+        by_guide: lru::LruCache::new(NonZeroUsize::new(GUIDE_CAPACITY).unwrap()),
+    }))
+}
+
+/// Remembers a rewrite of `req` and returns its id.
+pub async fn record(store: &FeedbackStore, req: &RewriteRequest, rewritten: &str, model: &str) -> String {
+    let opts = req.options.as_ref();
+    let rec = RewriteRecord {
+        guideId: req.brandGuide.id.clone(),
+        original: req.textToRewrite.clone(),
+        rewritten: rewritten.to_string(),
+        aggressiveness: opts.and_then(|o| o.aggressiveness).unwrap_or(DEFAULT_AGGRESSIVENESS),
+        model: model.to_string(),
+        channel: crate::channels::for_options(opts).map(|p| p.id.to_string()),
+    };
+    let id = crate::guides::new_id();
+    store.lock().await.rewrites.put(id.clone(), rec);
+    id
+}
+
+/// Stores the verdict (replacing an earlier one for the same rewrite) and returns the rewrite it applies to.
+/// Rewrites of a guide without an `id` have no stats to join, so their verdicts are not stored.
+pub async fn submit(store: &FeedbackStore, rewrite_id: &str, req: &FeedbackRequest) -> Option<RewriteRecord> {
+    let mut s = store.lock().await;
+    let rec = s.rewrites.get(rewrite_id)?.clone();
+    let Some(guide_id) = rec.guideId.clone() else { return Some(rec) };
+    let entry = FeedbackEntry {
+        rewriteId: rewrite_id.to_string(),
+        verdict: req.verdict,
+        aggressiveness: rec.aggressiveness,
+        model: rec.model.clone(),
+        channel: rec.channel.clone(),
+        comment: req.comment.clone().filter(|c| !c.trim().is_empty()),
+    };
+    let list = s.by_guide.get_or_insert_mut(guide_id, Vec::new);
+    list.retain(|e| e.rewriteId != rewrite_id);
+    list.push(entry);
+    if list.len() > FEEDBACK_PER_GUIDE { list.remove(0); }
+    Some(rec)
+}
+
+/// Acceptance stats for a guide, overall and by aggressiveness, model and channel.
+/// `acceptanceRate` counts edits as accepted (the rewrite was kept with changes).
+pub async fn stats(store: &FeedbackStore, guide_id: &str) -> Value {
+    let s = store.lock().await;
+    let entries: &[FeedbackEntry] = s.by_guide.peek(guide_id).map(|v| v.as_slice()).unwrap_or(&[]);
+    let group = |key: &dyn Fn(&FeedbackEntry) -> String| {
+        let mut keys: Vec<String> = entries.iter().map(key).collect();
+        keys.sort();
+        keys.dedup();
+        let map: serde_json::Map<String, Value> = keys.into_iter()
+            .map(|k| { let sub: Vec<&FeedbackEntry> = entries.iter().filter(|e| key(e) == k).collect(); (k, summary(&sub)) })
+            .collect();
+        Value::Object(map)
+    };
+    let mut out = summary(&entries.iter().collect::<Vec<_>>());
+    out["byAggressiveness"] = group(&|e| e.aggressiveness.to_string());
+    out["byModel"] = group(&|e| e.model.clone());
+    out["byChannel"] = group(&|e| e.channel.clone().unwrap_or_else(|| "none".into()));
+    out
+}
+
+fn summary(entries: &[&FeedbackEntry]) -> Value {
+    let count = |v: Verdict| entries.iter().filter(|e| e.verdict == v).count();
+    let (accepted, edited, rejected) = (count(Verdict::Accept), count(Verdict::Edit), count(Verdict::Reject));
+    let rate = if entries.is_empty() { json!(null) } else { json!(((accepted + edited) as f64 / entries.len() as f64 * 100.0).round() / 100.0) };
+    json!({"total": entries.len(), "accepted": accepted, "edited": edited, "rejected": rejected, "acceptanceRate": rate})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(aggressiveness: i32, channel: Option<&str>) -> RewriteRequest {
+        serde_json::from_value(json!({
+            "textToRewrite": "We leverage synergies.",
+            "brandGuide": {
+                "id": "g1", "brandName": "Acme", "industry": "Outdoor", "mission": "", "audience": "", "elevatorPitch": "",
+                "tone": {"traits": [], "description": "", "dosAndDonts": {"dos": [], "donts": []}}, "taglines": [], "palette": {}
+            },
+            "options": {"aggressiveness": aggressiveness, "channel": channel}
+        })).unwrap()
+    }
+
+    fn verdict(v: Verdict) -> FeedbackRequest { FeedbackRequest { verdict: v, editedText: None, comment: None } }
+
+    #[tokio::test]
+    async fn later_verdicts_replace_earlier_ones() {
+        let store = new_store();
+        let id = record(&store, &request(2, None), "We work together.", "gemini").await;
+        assert!(submit(&store, "unknown", &verdict(Verdict::Accept)).await.is_none());
+        submit(&store, &id, &verdict(Verdict::Reject)).await.unwrap();
+        let rec = submit(&store, &id, &verdict(Verdict::Accept)).await.unwrap();
+        assert_eq!(rec.guideId.as_deref(), Some("g1"));
+        let s = stats(&store, "g1").await;
+        assert_eq!((s["total"].clone(), s["accepted"].clone(), s["rejected"].clone()), (json!(1), json!(1), json!(0)));
+    }
+
+    #[tokio::test]
+    async fn verdicts_without_a_guide_are_not_stored() {
+        let store = new_store();
+        let mut req = request(2, None);
+        req.brandGuide.id = None;
+        let id = record(&store, &req, "rewritten", "mock").await;
+        // The rewrite is still found (kept rewrites need it), but no stats are filed
+        assert!(submit(&store, &id, &verdict(Verdict::Accept)).await.unwrap().guideId.is_none());
+        assert!(store.lock().await.by_guide.is_empty());
+        assert_eq!(stats(&store, "").await["total"], json!(0));
+    }
+
+    #[tokio::test]
+    async fn summarizes_acceptance_by_setting() {
+        let store = new_store();
+        assert_eq!(stats(&store, "g1").await["acceptanceRate"], json!(null));
+        let runs = [(1, None, "gemini", Verdict::Accept), (3, Some("sms"), "mock", Verdict::Edit), (3, Some("sms"), "mock", Verdict::Reject)];
+        for (aggr, channel, model, v) in runs {
+            let id = record(&store, &request(aggr, channel), "rewritten", model).await;
+            submit(&store, &id, &verdict(v)).await.unwrap();
+        }
+        let s = stats(&store, "g1").await;
+        // Edits count as accepted: 2 of 3
+        assert_eq!(s["acceptanceRate"], json!(0.67));
+        assert_eq!(s["byAggressiveness"]["3"]["total"], json!(2));
+        assert_eq!(s["byAggressiveness"]["1"]["acceptanceRate"], json!(1.0));
+        assert_eq!(s["byModel"]["mock"]["edited"], json!(1));
+        assert_eq!(s["byModel"]["mock"]["acceptanceRate"], json!(0.5));
+        assert_eq!(s["byChannel"]["none"]["total"], json!(1));
+        assert_eq!(s["byChannel"]["sms"]["rejected"], json!(1));
+    }
+}
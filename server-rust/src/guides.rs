@@ -0,0 +1,59 @@
+// In-memory guide store so exports and follow-up calls can reference a guide by id.
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+
+use serde_json::Value;
+
+// Oldest guides are evicted beyond this many entries
+const CAPACITY: usize = 1024;
+
+pub type GuideStore = Arc<tokio::sync::Mutex<lru::LruCache<String, Value>>>;
+
+pub fn new_store() -> GuideStore {
+    Arc::new(tokio::sync::Mutex::new(lru::LruCache::new(std::num::NonZeroUsize::new(CAPACITY).unwrap())))
+}
+
+// 16 hex chars from a randomly keyed hash of the clock and a counter
+pub fn new_id() -> String {
+    static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let mut h = std::collections::hash_map::RandomState::new().build_hasher();
+    h.write_u128(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0));
+    h.write_u64(COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
+    format!("{:016x}", h.finish())
+}
+
+/// Stores the guide under a fresh id, writes the id into it, and returns the id. Any `id` the guide
+/// already carries is replaced, so a create can never overwrite another guide.
+pub async fn create(store: &GuideStore, mut guide: Value) -> String {
+    let id = new_id();
+    set_id(&mut guide, &id);
+    store.lock().await.put(id.clone(), guide);
+    id
+}
+
+/// Replaces the guide stored under `id`; false (and nothing stored) when there is none.
+pub async fn update(store: &GuideStore, id: &str, mut guide: Value) -> bool {
+    set_id(&mut guide, id);
+    let mut store = store.lock().await;
+    if !store.contains(id) { return false; }
+    store.put(id.to_string(), guide);
+    true
+}
+
+/// Applies `f` to the stored guide while holding the store lock, so concurrent read-modify-write
+/// updates (exemplars, feedback, derived voice) can't overwrite each other. None when there is no such guide.
+pub async fn modify<R>(store: &GuideStore, id: &str, f: impl FnOnce(&mut Value) -> R) -> Option<R> {
+    let mut store = store.lock().await;
+    let guide = store.get_mut(id)?;
+    let out = f(guide);
+    set_id(guide, id);
+    Some(out)
+}
+
+fn set_id(guide: &mut Value, id: &str) {
+    if let Some(obj) = guide.as_object_mut() { obj.insert("id".to_string(), Value::String(id.to_string())); }
+}
+
+pub async fn get(store: &GuideStore, id: &str) -> Option<Value> {
+    store.lock().await.get(id).cloned()
+}
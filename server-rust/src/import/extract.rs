@@ -0,0 +1,140 @@
+// Local text extraction from uploaded guides, plus hex color mentions with their labels.
+use crate::export::zip;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat { Markdown, Text, Docx, Pdf }
+
+impl SourceFormat {
+    pub fn as_str(self) -> &'static str {
+        match self { SourceFormat::Markdown => "markdown", SourceFormat::Text => "text", SourceFormat::Docx => "docx", SourceFormat::Pdf => "pdf" }
+    }
+}
+
+// Keywords that name a palette role when they precede a hex code on the same line
+const ROLE_WORDS: &[(&str, &str)] = &[
+    ("primary", "primary"), ("secondary", "secondary"), ("accent", "accent"), ("highlight", "accent"),
+    ("background", "background"), ("link", "link"), ("body text", "text"), ("text", "text"),
+    ("neutral light", "neutralLight"), ("light neutral", "neutralLight"), ("neutral dark", "neutralDark"), ("dark neutral", "neutralDark"),
+];
+
+/// Format from the file name, then the content type, then magic bytes; None for unsupported binaries.
+pub fn detect(filename: Option<&str>, content_type: Option<&str>, bytes: &[u8]) -> Option<SourceFormat> {
+    let ext = filename.and_then(|f| f.rsplit_once('.')).map(|(_, e)| e.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("md" | "markdown") => return Some(SourceFormat::Markdown),
+        Some("txt") => return Some(SourceFormat::Text),
+        Some("docx") => return Some(SourceFormat::Docx),
+        Some("pdf") => return Some(SourceFormat::Pdf),
+        _ => {}
+    }
+    match content_type.unwrap_or("") {
+        "application/pdf" => return Some(SourceFormat::Pdf),
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => return Some(SourceFormat::Docx),
+        "text/markdown" => return Some(SourceFormat::Markdown),
+        _ => {}
+    }
+    if bytes.starts_with(b"%PDF") { return Some(SourceFormat::Pdf); }
+    if bytes.starts_with(b"PK\x03\x04") { return Some(SourceFormat::Docx); }
+    std::str::from_utf8(bytes).ok().map(|_| SourceFormat::Text)
+}
+
+pub fn extract(format: SourceFormat, bytes: &[u8]) -> Result<String, String> {
+    let text = match format {
+        SourceFormat::Markdown | SourceFormat::Text => String::from_utf8_lossy(bytes).into_owned(),
+        SourceFormat::Docx => {
+            let xml = zip::read_entry(bytes, "word/document.xml").ok_or("not a readable .docx (word/document.xml missing)")?;
+            docx_text(&String::from_utf8_lossy(&xml))
+        }
+        SourceFormat::Pdf => super::pdf::text(bytes),
+    };
+    if text.chars().filter(|c| c.is_alphabetic()).count() < 20 {
+        return Err(match format {
+            SourceFormat::Pdf => "no extractable text in this PDF (scanned pages or embedded CID fonts are not supported)".into(),
+            _ => "the document has no usable text".into(),
+        });
+    }
+    Ok(text)
+}
+
+// Paragraphs become lines and table cells are separated by " | "
+fn docx_text(xml: &str) -> String {
+    let mut out = String::new();
+    let mut rest = xml;
+    while let Some(lt) = rest.find('<') {
+        let Some(gt) = rest[lt..].find('>').map(|g| lt + g) else { break };
+        let tag = &rest[lt + 1..gt];
+        let name = tag.split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or("");
+        match name {
+            "w:t" if !tag.ends_with('/') => {
+                let end = rest[gt..].find("</w:t>").map(|e| gt + e).unwrap_or(rest.len());
+                out.push_str(&unescape(&rest[gt + 1..end]));
+                rest = &rest[end..];
+                continue;
+            }
+            "w:tab" => out.push('\t'),
+            "w:br" | "w:cr" => out.push('\n'),
+            "" if tag == "/w:p" => out.push('\n'),
+            "" if tag == "/w:tc" => { if out.ends_with('\n') { out.pop(); } out.push_str(" | "); }
+            "" if tag == "/w:tr" => { while out.ends_with([' ', '|']) { out.pop(); } out.push('\n'); }
+            _ => {}
+        }
+        rest = &rest[gt + 1..];
+    }
+    out
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&apos;", "'").replace("&amp;", "&")
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ColorMention { pub hex: String, pub context: String, pub role: Option<String> }
+
+/// Distinct `#RRGGBB`/`#RGB` codes in document order, with the line they appear on and a role
+/// when a role keyword sits between the previous code and this one.
+pub fn find_colors(text: &str) -> Vec<ColorMention> {
+    let mut out: Vec<ColorMention> = Vec::new();
+    for line in text.lines() {
+        let mut label_from = 0;
+        for (i, _) in line.match_indices('#') {
+            let digits = line[i + 1..].chars().take_while(|c| c.is_ascii_alphanumeric()).collect::<String>();
+            if !(digits.len() == 6 || digits.len() == 3) || !digits.chars().all(|c| c.is_ascii_hexdigit()) { continue; }
+            let Some(rgb) = crate::palette::color::parse_hex(&digits) else { continue };
+            let hex = crate::palette::color::to_hex(rgb);
+            let label = line[label_from..i].to_lowercase();
+            label_from = i + 1 + digits.len();
+            if out.iter().any(|c| c.hex == hex) { continue; }
+            // The last keyword before the code wins ("Text on primary: #fff" → text is ambiguous, primary is nearer)
+            let role = ROLE_WORDS.iter().filter_map(|(k, r)| label.rfind(k).map(|p| (p + k.len(), *r))).max_by_key(|(p, _)| *p).map(|(_, r)| {
+                if label.contains("dark mode") || label.contains("(dark)") { format!("{}Dark", r) } else { r.to_string() }
+            });
+            out.push(ColorMention { hex, context: line.trim().chars().take(160).collect(), role });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BrandGuide;
+
+    #[test]
+    fn reads_back_exported_guides_and_labels_colors() {
+        let guide: BrandGuide = serde_json::from_value(serde_json::json!({
+            "brandName": "Harbor & Pine", "industry": "Dental care", "mission": "Calm, honest dental care for families (really).",
+            "audience": "Families in the valley.", "elevatorPitch": "We keep visits short.",
+            "tone": {"traits": ["Warm"], "description": "Friendly and direct.", "dosAndDonts": {"dos": ["Use first names"], "donts": ["Jargon"]}},
+            "taglines": [], "palette": {"primary": "#1E6B52", "background": "#FAFAF7"}
+        })).unwrap();
+        let pdf = extract(SourceFormat::Pdf, &crate::export::brandbook::render(&guide, None)).unwrap();
+        assert!(pdf.contains("Calm, honest dental care for families (really)."), "{}", pdf);
+        assert!(pdf.contains("#1E6B52"));
+        let docx = extract(SourceFormat::Docx, &crate::export::docx::render(&guide)).unwrap();
+        assert!(docx.contains("Use first names | Jargon\n"), "{}", docx);
+
+        let colors = find_colors("Primary green: #1e6b52, accent #F0A\nBackground (dark mode) #111111 and again #1E6B52\n## Heading #notahex");
+        let got: Vec<(&str, Option<&str>)> = colors.iter().map(|c| (c.hex.as_str(), c.role.as_deref())).collect();
+        assert_eq!(got, vec![("#1E6B52", Some("primary")), ("#FF00AA", Some("accent")), ("#111111", Some("backgroundDark"))]);
+    }
+}
@@ -0,0 +1,124 @@
+// Guide import: text is extracted locally, mapped onto BrandGuide fields by the model (guide_schema),
+// and palette colors come from hex codes actually present in the document.
+pub mod extract;
+pub mod pdf;
+
+use serde_json::{json, Map, Value};
+
+use crate::adapters::AdapterDyn;
+use extract::{ColorMention, SourceFormat};
+
+// Longer documents are truncated before prompting
+const MAX_SOURCE_CHARS: usize = 40_000;
+const TEXT_FIELDS: &[&str] = &["brandName", "industry", "mission", "audience", "elevatorPitch"];
+const LIST_FIELDS: &[&str] = &["taglines", "bannedTerms", "preferredTerms"];
+
+fn import_schema() -> Value {
+    let mut guide = crate::adapters::schemas::guide_schema();
+    // Imports report what the document contains; never pad taglines to a minimum
+    if let Some(t) = guide["properties"]["taglines"].as_object_mut() { t.remove("minItems"); }
+    guide["properties"]["bannedTerms"] = json!({"type": "array", "items": {"type": "string"}});
+    guide["properties"]["preferredTerms"] = json!({"type": "array", "items": {"type": "object", "additionalProperties": false, "required": ["avoid", "prefer"], "properties": {"avoid": {"type": "string"}, "prefer": {"type": "string"}}}});
+    let fields: Vec<&str> = TEXT_FIELDS.iter().chain(LIST_FIELDS).copied().chain(["tone"]).collect();
+    let confidence: Map<String, Value> = fields.iter().map(|f| (f.to_string(), json!({"type": "number"}))).collect();
+    json!({
+        "type": "object", "additionalProperties": false, "required": ["guide", "confidence", "palette"],
+        "properties": {
+            "guide": guide,
+            "confidence": {"type": "object", "additionalProperties": false, "required": fields, "properties": confidence},
+            "palette": {"type": "array", "items": {"type": "object", "additionalProperties": false, "required": ["role", "hex"], "properties": {"role": {"type": "string"}, "hex": {"type": "string"}}}}
+        }
+    })
+}
+
+fn build_prompt(text: &str, colors: &[ColorMention]) -> String {
+    let color_lines: Vec<String> = colors.iter().map(|c| format!("- {} (\"{}\")", c.hex, c.context)).collect();
+    format!(
+        "Map this existing brand guideline document onto the brand guide fields.\n\
+Only use what the document says: copy wording where possible, leave a string empty or a list empty when the document does not cover it, and never invent taglines or terms.\n\
+tone.traits are short adjectives; tone.dosAndDonts come from explicit do/don't guidance. bannedTerms are words the document says to avoid; preferredTerms are explicit \"say X, not Y\" swaps.\n\
+For each field give confidence from 0 to 1 (1 = stated explicitly, 0.5 = inferred, 0 = not present).\n\
+palette: assign roles (primary, secondary, accent, background, text, link, neutralLight, neutralDark, or a *Dark variant) only to these colors found in the document:\n{}\n\n\
+Document:\n\"\"\"\n{}\n\"\"\"",
+        if color_lines.is_empty() { "(none)".to_string() } else { color_lines.join("\n") },
+        text
+    )
+}
+
+/// Draft guide with per-field confidence; fields the document doesn't cover are listed in `missing`.
+pub async fn import(adapter: &AdapterDyn, format: SourceFormat, text: &str) -> Value {
+    let colors = extract::find_colors(text);
+    let truncated = text.chars().count() > MAX_SOURCE_CHARS;
+    let source: String = text.chars().take(MAX_SOURCE_CHARS).collect();
+    let mapped = match adapter.generate_json(&build_prompt(&source, &colors), Some(import_schema()), Some(0.1)).await {
+        Ok(v) => Some(v),
+        Err(e) => { tracing::warn!("import: model mapping failed, returning colors only: {}", e); None }
+    };
+    let data = mapped.clone().unwrap_or(json!({}));
+    let g = &data["guide"];
+    let normalized_source = normalize(text);
+
+    let mut guide = json!({
+        "brandName": "", "industry": "", "logoUrl": null, "mission": "", "audience": "", "elevatorPitch": "",
+        "tone": {"traits": [], "description": "", "dosAndDonts": {"dos": [], "donts": []}},
+        "taglines": [], "bannedTerms": [], "preferredTerms": [], "palette": {},
+    });
+    let mut fields = Map::new();
+    let mut missing = Vec::new();
+    let mut record = |name: &str, found: bool, verbatim: Option<bool>| {
+        let model = data["confidence"][name].as_f64().unwrap_or(0.5).clamp(0.0, 1.0);
+        let confidence = if !found { 0.0 } else if verbatim == Some(true) { model.max(0.9) } else { model };
+        let mut entry = json!({"found": found, "confidence": (confidence * 100.0).round() / 100.0});
+        if let Some(v) = verbatim { entry["verbatim"] = json!(v); }
+        fields.insert(name.to_string(), entry);
+        if !found { missing.push(name.to_string()); }
+    };
+    for f in TEXT_FIELDS {
+        let v = g[*f].as_str().unwrap_or("").trim().to_string();
+        let verbatim = !v.is_empty() && normalized_source.contains(&normalize(&v));
+        record(f, !v.is_empty(), Some(verbatim));
+        guide[*f] = json!(v);
+    }
+    for f in LIST_FIELDS {
+        let v: Vec<Value> = g[*f].as_array().cloned().unwrap_or_default();
+        record(f, !v.is_empty(), None);
+        guide[*f] = json!(v);
+    }
+    let tone = &g["tone"];
+    let tone_found = tone["traits"].as_array().is_some_and(|a| !a.is_empty()) || tone["description"].as_str().is_some_and(|s| !s.trim().is_empty());
+    if tone.is_object() { guide["tone"] = tone.clone(); }
+    record("tone", tone_found, None);
+
+    // Labels in the document win; the model may only assign roles to codes that were found
+    let mut palette = Map::new();
+    for c in &colors {
+        if let Some(r) = &c.role { palette.entry(r.clone()).or_insert_with(|| json!(c.hex)); }
+    }
+    for p in data["palette"].as_array().into_iter().flatten() {
+        let (Some(role), Some(hex)) = (p["role"].as_str(), p["hex"].as_str().and_then(crate::palette::color::parse_hex)) else { continue };
+        let hex = crate::palette::color::to_hex(hex);
+        if role.is_empty() || palette.contains_key(role) || palette.values().any(|v| v == &json!(hex)) || !colors.iter().any(|c| c.hex == hex) { continue; }
+        palette.insert(role.to_string(), json!(hex));
+    }
+    let labeled = colors.iter().all(|c| c.role.is_some());
+    let palette_confidence = if palette.is_empty() { 0.0 } else if labeled { 0.9 } else { 0.7 };
+    fields.insert("palette".into(), json!({"found": !palette.is_empty(), "confidence": palette_confidence}));
+    if palette.is_empty() { missing.push("palette".into()); }
+    let unassigned: Vec<&str> = colors.iter().map(|c| c.hex.as_str()).filter(|h| !palette.values().any(|v| v == h)).collect();
+    let unassigned = json!(unassigned);
+    guide["palette"] = Value::Object(palette);
+
+    json!({
+        "guide": guide,
+        "fields": fields,
+        "missing": missing,
+        "colors": colors,
+        "unassignedColors": unassigned,
+        "mapped": mapped.is_some(),
+        "source": {"format": format.as_str(), "characters": text.chars().count(), "truncated": truncated},
+    })
+}
+
+fn normalize(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
@@ -0,0 +1,221 @@
+// Best-effort text extraction from PDF content streams: Flate-compressed or raw streams, simple
+// (single-byte) font encodings read as WinAnsi. CID fonts and scanned pages yield little or no text.
+use std::io::Read;
+
+const MAX_STREAM_BYTES: u64 = 16 << 20;
+// Budgets across all streams: many small Flate streams must not add up to gigabytes. The text budget
+// stays well above import's MAX_SOURCE_CHARS so truncation there is still reported.
+const MAX_TOTAL_BYTES: u64 = 64 << 20;
+const MAX_TEXT_BYTES: usize = 512 << 10;
+
+// WinAnsi 0x80–0x9F; undefined slots map to a space
+const WIN_ANSI_HIGH: [char; 32] = [
+    '€', ' ', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', ' ', 'Ž', ' ',
+    ' ', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', ' ', 'ž', 'Ÿ',
+];
+
+pub fn text(pdf: &[u8]) -> String {
+    let mut out = String::new();
+    let mut budget = MAX_TOTAL_BYTES;
+    let mut at = 0;
+    while let Some(pos) = find(pdf, b"stream", at) {
+        if budget == 0 || out.len() >= MAX_TEXT_BYTES { break; }
+        at = pos + 6;
+        // Skip "endstream" and anything that isn't a stream keyword after a dictionary
+        if pos >= 3 && &pdf[pos - 3..pos] == b"end" { continue; }
+        let Some(dict_start) = rfind(&pdf[..pos], b"obj") else { continue };
+        let dict = &pdf[dict_start..pos];
+        let mut start = at;
+        if pdf.get(start) == Some(&b'\r') { start += 1; }
+        if pdf.get(start) == Some(&b'\n') { start += 1; }
+        let Some(end) = find(pdf, b"endstream", start) else { break };
+        at = end + 9;
+        if [&b"/Image"[..], b"/FontFile", b"/Length1", b"/XRef", b"/ObjStm", b"/Metadata"].iter().any(|k| find(dict, k, 0).is_some()) { continue; }
+        let raw = &pdf[start..end];
+        let data = if find(dict, b"/FlateDecode", 0).is_some() {
+            let mut buf = Vec::new();
+            if flate2::read::ZlibDecoder::new(raw).take(MAX_STREAM_BYTES.min(budget)).read_to_end(&mut buf).is_err() { continue; }
+            buf
+        } else if find(dict, b"/Filter", 0).is_none() {
+            raw.to_vec()
+        } else {
+            continue;
+        };
+        budget = budget.saturating_sub(data.len() as u64);
+        if find(&data, b"BT", 0).is_some() { content_text(&data, &mut out); }
+    }
+    let mut out = out.trim().to_string();
+    if out.len() > MAX_TEXT_BYTES { out.truncate((0..=MAX_TEXT_BYTES).rev().find(|&i| out.is_char_boundary(i)).unwrap_or(0)); }
+    out
+}
+
+enum Operand { Num(f32), Str(Vec<u8>), Array(Vec<Operand>), Other }
+
+// Walks the operators, emitting strings from Tj/TJ/'/" and line breaks when the baseline moves
+fn content_text(data: &[u8], out: &mut String) {
+    let mut ops: Vec<Operand> = Vec::new();
+    let (mut line_y, mut y, mut pending_break) = (f32::NAN, 0.0f32, false);
+    let mut i = 0;
+    while i < data.len() {
+        let c = data[i];
+        if c.is_ascii_whitespace() { i += 1; continue; }
+        if c == b'%' { while i < data.len() && data[i] != b'\n' { i += 1; } continue; }
+        if c == b'[' { i += 1; let (arr, next) = array(data, i); ops.push(Operand::Array(arr)); i = next; continue; }
+        if let Some((op, next)) = operand(data, i) { ops.push(op); i = next; continue; }
+        let start = i;
+        while i < data.len() && !data[i].is_ascii_whitespace() && !b"()<>[]{}/%".contains(&data[i]) { i += 1; }
+        if i == start { i += 1; ops.clear(); continue; }
+        let nums: Vec<f32> = ops.iter().filter_map(|o| if let Operand::Num(n) = o { Some(*n) } else { None }).collect();
+        match &data[start..i] {
+            b"BT" => y = 0.0,
+            b"Td" | b"TD" => if let Some(ty) = nums.last() { y += ty },
+            b"Tm" => if let Some(ty) = nums.last() { y = *ty },
+            b"T*" => pending_break = true,
+            b"Tj" | b"'" | b"\"" => {
+                if &data[start..i] != b"Tj" { pending_break = true; }
+                if let Some(Operand::Str(s)) = ops.last() { emit(out, &decode(s), y, &mut line_y, &mut pending_break); }
+            }
+            b"TJ" => if let Some(Operand::Array(items)) = ops.last() {
+                let mut s = String::new();
+                for it in items {
+                    match it {
+                        Operand::Str(b) => s.push_str(&decode(b)),
+                        // Large negative adjustments stand in for word spaces
+                        Operand::Num(n) if *n < -200.0 && !s.ends_with(' ') => s.push(' '),
+                        _ => {}
+                    }
+                }
+                emit(out, &s, y, &mut line_y, &mut pending_break);
+            },
+            _ => {}
+        }
+        ops.clear();
+    }
+    if !out.is_empty() && !out.ends_with('\n') { out.push('\n'); }
+}
+
+// Appends a text run, starting a new line when the baseline moved or a line-break operator ran
+fn emit(out: &mut String, s: &str, y: f32, line_y: &mut f32, pending_break: &mut bool) {
+    if !line_y.is_nan() && ((y - *line_y).abs() > 1.0 || *pending_break) { out.push('\n'); }
+    else if !out.is_empty() && !out.ends_with([' ', '\n']) && !line_y.is_nan() { out.push(' '); }
+    out.push_str(s);
+    *line_y = y;
+    *pending_break = false;
+}
+
+fn array(data: &[u8], mut i: usize) -> (Vec<Operand>, usize) {
+    let mut items = Vec::new();
+    while i < data.len() {
+        if data[i].is_ascii_whitespace() { i += 1; continue; }
+        if data[i] == b']' { return (items, i + 1); }
+        match operand(data, i) {
+            Some((op, next)) => { items.push(op); i = next; }
+            None => i += 1,
+        }
+    }
+    (items, i)
+}
+
+// Number, string or name at `i`; None when an operator (or anything else) starts there
+fn operand(data: &[u8], i: usize) -> Option<(Operand, usize)> {
+    let c = data[i];
+    match c {
+        b'(' => Some(literal(data, i + 1)),
+        b'<' if data.get(i + 1) == Some(&b'<') => Some((Operand::Other, i + 2)),
+        b'>' if data.get(i + 1) == Some(&b'>') => Some((Operand::Other, i + 2)),
+        b'<' => {
+            let end = data[i..].iter().position(|&b| b == b'>').map(|p| i + p).unwrap_or(data.len());
+            let hex: Vec<u8> = data[i + 1..end].iter().copied().filter(|b| b.is_ascii_hexdigit()).collect();
+            let bytes = hex.chunks(2).filter_map(|p| u8::from_str_radix(&format!("{:0<2}", String::from_utf8_lossy(p)), 16).ok()).collect();
+            Some((Operand::Str(bytes), end + 1))
+        }
+        b'/' => {
+            let mut j = i + 1;
+            while j < data.len() && !data[j].is_ascii_whitespace() && !b"()<>[]{}/%".contains(&data[j]) { j += 1; }
+            Some((Operand::Other, j))
+        }
+        b'0'..=b'9' | b'-' | b'+' | b'.' => {
+            let mut j = i + 1;
+            while j < data.len() && (data[j].is_ascii_digit() || data[j] == b'.') { j += 1; }
+            let n = std::str::from_utf8(&data[i..j]).ok()?.parse().ok()?;
+            Some((Operand::Num(n), j))
+        }
+        _ => None,
+    }
+}
+
+// Literal string body starting after '(' with nesting and escapes
+fn literal(data: &[u8], mut i: usize) -> (Operand, usize) {
+    let (mut out, mut depth) = (Vec::new(), 1);
+    while i < data.len() {
+        let c = data[i];
+        i += 1;
+        match c {
+            b'\\' => {
+                let Some(&e) = data.get(i) else { break };
+                i += 1;
+                match e {
+                    b'n' => out.push(b'\n'), b'r' => out.push(b'\r'), b't' => out.push(b'\t'),
+                    b'b' | b'f' => {}
+                    b'0'..=b'7' => {
+                        let mut v = (e - b'0') as u32;
+                        for _ in 0..2 {
+                            match data.get(i) { Some(d @ b'0'..=b'7') => { v = v * 8 + (d - b'0') as u32; i += 1; } _ => break }
+                        }
+                        out.push(v as u8);
+                    }
+                    b'\r' | b'\n' => { if e == b'\r' && data.get(i) == Some(&b'\n') { i += 1; } }
+                    _ => out.push(e),
+                }
+            }
+            b'(' => { depth += 1; out.push(c); }
+            b')' => { depth -= 1; if depth == 0 { break; } out.push(c); }
+            _ => out.push(c),
+        }
+    }
+    (Operand::Str(out), i)
+}
+
+fn decode(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| match b {
+        0x80..=0x9F => WIN_ANSI_HIGH[(b - 0x80) as usize],
+        b'\t' | b'\n' | b'\r' => ' ',
+        0..=0x1F => ' ',
+        _ => b as char,
+    }).collect()
+}
+
+fn find(hay: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    hay.get(from..)?.windows(needle.len()).position(|w| w == needle).map(|p| p + from)
+}
+
+fn rfind(hay: &[u8], needle: &[u8]) -> Option<usize> {
+    hay.windows(needle.len()).rposition(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flate_stream(content: &[u8]) -> Vec<u8> {
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+        std::io::Write::write_all(&mut enc, content).unwrap();
+        let data = enc.finish().unwrap();
+        let mut out = format!("1 0 obj\n<< /Length {} /Filter /FlateDecode >>\nstream\n", data.len()).into_bytes();
+        out.extend(data);
+        out.extend(b"\nendstream\nendobj\n");
+        out
+    }
+
+    #[test]
+    fn text_is_capped_across_streams() {
+        let pdf = [b"%PDF-1.4\n".to_vec(), flate_stream(b"BT /F1 12 Tf 72 720 Td (Brand voice) Tj ET")].concat();
+        assert_eq!(text(&pdf), "Brand voice");
+
+        // Each stream inflates to 64 KB of text; together they would be 6 MB
+        let line = format!("BT ({}) Tj ET", "a".repeat(64 << 10));
+        let many: Vec<u8> = std::iter::repeat_with(|| flate_stream(line.as_bytes())).take(100).flatten().collect();
+        let out = text(&[b"%PDF-1.4\n".to_vec(), many].concat());
+        assert!(out.len() <= MAX_TEXT_BYTES && out.len() > MAX_TEXT_BYTES / 2, "{}", out.len());
+    }
+}
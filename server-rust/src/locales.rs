@@ -0,0 +1,66 @@
+// Resolves a guide's per-locale voice rules for the language a text is written (or translated) in.
+use serde_json::{json, Value};
+
+use crate::{models::{BrandGuide, LocaleVoice, RewriteRequest, UserInputs}, text::lang};
+
+/// Guide locale matching `tag`: exact tag first ("fr-CA"), then the same primary language ("fr").
+pub fn find<'a>(locales: &'a [LocaleVoice], tag: &str) -> Option<&'a LocaleVoice> {
+    locales.iter().find(|l| l.locale.trim().eq_ignore_ascii_case(tag.trim()))
+        .or_else(|| locales.iter().find(|l| lang::primary(&l.locale) == lang::primary(tag)))
+}
+
+/// Copy of the guide with the locale's banned terms, preferred vocabulary and tone notes folded in.
+/// The result has no `locales`, so localizing it again is a no-op. The built-in banned list for the
+/// language is not included; callers pass the language to `prompts::banned_terms`.
+pub fn localize(guide: &BrandGuide, tag: &str) -> BrandGuide {
+    let mut g = guide.clone();
+    if let Some(l) = find(&guide.locales, tag) {
+        g.bannedTerms.extend(l.bannedTerms.iter().cloned());
+        g.preferredTerms.extend(l.preferredTerms.iter().cloned());
+        if let Some(notes) = l.toneNotes.as_deref().filter(|n| !n.trim().is_empty()) {
+            g.tone.description = format!("{}\n{} ({}) voice notes: {}", g.tone.description, lang::name(&l.locale), l.locale, notes.trim());
+        }
+    }
+    g.locales.clear();
+    g
+}
+
+/// Language a guide is generated in (`inputs.locale`, English when unset) and the guide's banned terms
+/// plus those of the matching `locales` entry. The built-in list for the language is not included.
+pub fn generation_banned(inputs: &UserInputs) -> (String, Vec<String>) {
+    let tag = inputs.locale.as_deref().map(str::trim).filter(|l| !l.is_empty()).unwrap_or("en").to_string();
+    let mut extra = inputs.bannedTerms.clone();
+    if let Some(l) = find(&inputs.locales, &tag) { extra.extend(l.bannedTerms.iter().cloned()); }
+    (tag, extra)
+}
+
+/// Rewrite request with the guide localized for the output language: `targetLocale` when set,
+/// otherwise the detected language of the source text.
+pub fn localize_rewrite(req: &RewriteRequest) -> RewriteRequest {
+    match output_language(req) {
+        Some(tag) => RewriteRequest { brandGuide: localize(&req.brandGuide, &tag), ..req.clone() },
+        None => req.clone(),
+    }
+}
+
+/// Language a rewrite is written in: `targetLocale` when set, otherwise the detected source language.
+pub fn output_language(req: &RewriteRequest) -> Option<String> {
+    req.options.as_ref().and_then(|o| o.targetLocale.clone()).filter(|l| !l.trim().is_empty())
+        .or_else(|| lang::detect(&req.textToRewrite).map(|d| d.language.to_string()))
+}
+
+/// Language of `text` (explicit locale wins over detection) and the guide locale it maps to.
+pub fn resolve(text: &str, guide: &BrandGuide, explicit: Option<&str>) -> (Option<String>, Value) {
+    let detected = lang::detect(text);
+    let tag = explicit.filter(|l| !l.trim().is_empty()).map(|l| l.trim().to_string())
+        .or_else(|| detected.as_ref().map(|d| d.language.to_string()));
+    let matched = tag.as_deref().and_then(|t| find(&guide.locales, t)).map(|l| l.locale.clone());
+    let info = json!({
+        "language": tag.as_deref().map(lang::primary),
+        "name": tag.as_deref().map(lang::name),
+        "detected": detected,
+        "explicit": explicit.is_some(),
+        "guideLocale": matched,
+    });
+    (tag, info)
+}
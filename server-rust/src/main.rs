@@ -8,6 +8,18 @@ mod prompts;
 mod adapters;
 mod agents;
 mod routes;
+mod text;
+mod consistency;
+mod rewrite;
+mod channels;
+mod locales;
+mod guides;
+mod palette;
+mod export;
+mod import;
+mod voice;
+mod exemplars;
+mod feedback;
 
 use adapters::{Provider, make_adapter, AdapterDyn};
 use routes::{health, generate_guide, rewrite_text, check_consistency};
@@ -16,6 +28,8 @@ use routes::{health, generate_guide, rewrite_text, check_consistency};
 pub struct AppState {
     pub adapter: Arc<AdapterDyn>,
     pub palette_cache: Arc<tokio::sync::Mutex<lru::LruCache<String, serde_json::Value>>>,
+    pub guides: guides::GuideStore,
+    pub feedback: feedback::FeedbackStore,
 }
 
 #[tokio::main]
@@ -56,7 +70,7 @@ async fn run() -> anyhow::Result<()> {
 
     // Simple in-memory LRU cache for palette suggestions (capacity ~256 entries)
     let cache = lru::LruCache::new(std::num::NonZeroUsize::new(256).unwrap());
-    let state = AppState { adapter: Arc::from(adapter), palette_cache: Arc::new(tokio::sync::Mutex::new(cache)) };
+    let state = AppState { adapter: Arc::from(adapter), palette_cache: Arc::new(tokio::sync::Mutex::new(cache)), guides: guides::new_store(), feedback: feedback::new_store() };
 
     tracing::info!("Boot: building router and CORS layer");
     let cors = CorsLayer::new().allow_origin(Any).allow_methods(Any).allow_headers(Any);
@@ -65,9 +79,26 @@ async fn run() -> anyhow::Result<()> {
         .route("/api/health", get(health))
         .route("/healthz", get(health))
         .route("/api/generate-guide", post(generate_guide))
+        .route("/api/guides", post(routes::guides::save_guide))
+        .route("/api/guides/import", post(routes::guides::import_guide).layer(axum::extract::DefaultBodyLimit::max(routes::guides::IMPORT_MAX_BYTES)))
+        .route("/api/guides/:id", get(routes::guides::get_guide).put(routes::guides::update_guide))
+        .route("/api/guides/:id/export.pdf", get(routes::guides::export_guide_pdf))
+        .route("/api/guides/:id/export.md", get(routes::guides::export_guide_markdown))
+        .route("/api/guides/:id/export.docx", get(routes::guides::export_guide_docx))
+        .route("/api/guides/:id/tokens", get(routes::guides::export_guide_tokens))
+        .route("/api/guides/:id/exemplars", post(routes::guides::add_exemplars))
+        .route("/api/guides/:id/feedback", get(routes::guides::feedback_stats))
+        .route("/api/tokens", post(routes::guides::export_tokens))
         .route("/api/rewrite", post(rewrite_text))
+        .route("/api/rewrite/ws", get(routes::ws_rewrite))
+        .route("/api/rewrite/:id/feedback", post(routes::guides::rewrite_feedback))
+        .route("/api/channels", get(routes::list_channels))
         .route("/api/consistency", post(check_consistency))
+        .route("/api/lint", post(routes::lint_text))
+        .route("/api/voice/derive", post(routes::derive_voice))
         .route("/api/suggest-palette", post(routes::suggest_palette))
+        .route("/api/palette/audit", post(routes::palette::audit_palette))
+        .route("/api/palette/cvd", post(routes::palette::simulate_cvd))
         .route("/api/orchestrate", get(routes::ws_orchestrate))
         .with_state(state)
         .layer(cors);
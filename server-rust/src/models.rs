@@ -14,9 +14,42 @@ pub struct DosAndDonts { pub dos: Vec<String>, pub donts: Vec<String> }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tone { pub traits: Vec<String>, pub description: String, pub dosAndDonts: DosAndDonts }
 
+// Guide-specific vocabulary swap, e.g. say "members" instead of "customers"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreferredTerm { pub avoid: String, pub prefer: String }
+
+// CSS font-family stacks for headings and body copy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontPairing { pub name: Option<String>, pub heading: String, pub body: String }
+
+// Per-locale voice rules, e.g. fr-CA tone notes and French banned terms
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocaleVoice {
+    pub locale: String,
+    pub toneNotes: Option<String>,
+    #[serde(default)]
+    pub bannedTerms: Vec<String>,
+    #[serde(default)]
+    pub preferredTerms: Vec<PreferredTerm>,
+}
+
+// Approved voice example: on-brand copy, optionally paired with the off-brand text it replaced
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Exemplar {
+    pub onBrand: String,
+    pub offBrand: Option<String>,
+    pub note: Option<String>,
+    // Where the pair came from, e.g. "rewrite" or "manual"
+    pub source: Option<String>,
+}
+
 #[allow(non_snake_case)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BrandGuide {
+    // Set once the guide is stored (POST /api/guides); rewrite feedback is attributed to it
+    pub id: Option<String>,
     pub brandName: String,
     pub industry: String,
     pub logoUrl: Option<String>,
@@ -26,6 +59,32 @@ pub struct BrandGuide {
     pub taglines: Vec<Tagline>,
     pub elevatorPitch: String,
     pub palette: Palette,
+    // Merged with the built-in list for the text's language (prompts::builtin_banned) wherever the guide is applied
+    #[serde(default)]
+    pub bannedTerms: Vec<String>,
+    #[serde(default)]
+    pub preferredTerms: Vec<PreferredTerm>,
+    pub readability: Option<ReadabilityTargets>,
+    // Supported locales beyond the guide's base language
+    #[serde(default)]
+    pub locales: Vec<LocaleVoice>,
+    #[serde(default)]
+    pub fontPairings: Vec<FontPairing>,
+    // Few-shot examples; the most relevant few are added to rewrite and consistency prompts
+    #[serde(default)]
+    pub exemplars: Vec<Exemplar>,
+}
+
+// Target ranges for check_consistency's readability metrics; unset fields fall back to defaults
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReadabilityTargets {
+    pub gradeMin: Option<f32>,
+    pub gradeMax: Option<f32>,
+    pub maxAvgSentenceLength: Option<f32>,
+    pub longSentenceWords: Option<usize>,
+    pub maxLongSentenceShare: Option<f32>,
+    pub maxPassiveRatio: Option<f32>,
 }
 
 #[allow(non_snake_case)]
@@ -40,6 +99,16 @@ pub struct UserInputs {
     pub audience: String,
     pub toneTraits: Vec<String>,
     pub palette: Palette,
+    #[serde(default)]
+    pub bannedTerms: Vec<String>,
+    #[serde(default)]
+    pub preferredTerms: Vec<PreferredTerm>,
+    #[serde(default)]
+    pub locales: Vec<LocaleVoice>,
+    // Where the brand operates (e.g. "Canada", "Pacific Northwest"); used by deterministic fallbacks
+    pub region: Option<String>,
+    // Primary locale of the guide (e.g. "en-CA", "fr-CA"); selects the fallback template set
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,13 +120,44 @@ pub struct GenerateGuideRequest { pub provider: Option<String>, pub inputs: User
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case)]
-pub struct RewriteOptions { pub aggressiveness: Option<i32>, pub keepLength: Option<bool>, pub preserveStructure: Option<bool>, pub notes: Option<String> }
+pub struct RewriteOptions {
+    pub aggressiveness: Option<i32>,
+    pub keepLength: Option<bool>,
+    pub preserveStructure: Option<bool>,
+    pub notes: Option<String>,
+    // Number of candidate rewrites to generate, score and rank (1 = single rewrite)
+    pub variants: Option<u32>,
+    // Include a word-level diff (spans, HTML, change ratio) against the original
+    pub diff: Option<bool>,
+    // keepLength tolerance as a fraction of the original word count (default 0.2 = ±20%)
+    pub lengthTolerance: Option<f32>,
+    // Corrective retries when keepLength/preserveStructure validation fails (default 1, at most 3)
+    pub maxRetries: Option<u32>,
+    // Target channel profile (x, linkedin, email_subject, email_body, push, google_ads_headline, google_ads_description, sms)
+    pub channel: Option<String>,
+    // Translate and adapt into this locale (e.g. "fr-CA") instead of rewriting in the source language
+    pub targetLocale: Option<String>,
+}
 
 #[allow(non_snake_case)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RewriteRequest { pub provider: Option<String>, pub textToRewrite: String, pub brandGuide: BrandGuide, pub options: Option<RewriteOptions> }
 
+// `language` (e.g. "fr-CA") selects the built-in banned list and turns off English-only rules; detected when unset
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintOptions { pub maxSentenceWords: Option<usize>, pub exclamations: Option<bool>, pub passiveVoice: Option<bool>, pub language: Option<String> }
+
 #[allow(non_snake_case)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConsistencyRequest { pub provider: Option<String>, pub textToCheck: String, pub brandGuide: BrandGuide }
+pub struct LintRequest { pub text: String, pub brandGuide: Option<BrandGuide>, pub options: Option<LintOptions> }
 
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsistencyRequest { pub provider: Option<String>, pub textToCheck: String, pub brandGuide: BrandGuide, pub locale: Option<String> }
+
+
+// Approved samples to derive a voice from; the result merges into the stored guide (guideId) or the inline one
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceDeriveRequest { pub provider: Option<String>, pub samples: Vec<String>, pub guideId: Option<String>, pub brandGuide: Option<BrandGuide> }
@@ -0,0 +1,52 @@
+// APCA (WCAG 3 draft, 0.0.98G-4g constants) lightness contrast. Lc is signed: positive for dark text on a
+// light background, negative for light text on dark; thresholds compare the magnitude.
+use super::color::Rgb;
+
+// Screen luminance with APCA's simple 2.4 exponent (not the piecewise sRGB curve)
+fn y((r, g, b): Rgb) -> f32 {
+    let lin = |c: u8| (c as f32 / 255.0).powf(2.4);
+    let y = 0.212_672_9 * lin(r) + 0.715_152_2 * lin(g) + 0.072_175 * lin(b);
+    // Soft clamp near black
+    if y < 0.022 { y + (0.022 - y).powf(1.414) } else { y }
+}
+
+/// Lc of `text` on `bg`, roughly -108 to 106.
+pub fn lc(text: Rgb, bg: Rgb) -> f32 {
+    let (yt, yb) = (y(text), y(bg));
+    if (yb - yt).abs() < 0.0005 { return 0.0; }
+    let out = if yb > yt {
+        let sapc = (yb.powf(0.56) - yt.powf(0.57)) * 1.14;
+        if sapc < 0.1 { 0.0 } else { sapc - 0.027 }
+    } else {
+        let sapc = (yb.powf(0.65) - yt.powf(0.62)) * 1.14;
+        if sapc > -0.1 { 0.0 } else { sapc + 0.027 }
+    };
+    out * 100.0
+}
+
+/// Use an Lc (either sign) supports, per the APCA bronze-level guidance.
+pub fn rating(lc: f32) -> &'static str {
+    let lc = lc.abs();
+    if lc >= 90.0 { "Preferred body" }
+    else if lc >= 75.0 { "Body" }
+    else if lc >= 60.0 { "Content" }
+    else if lc >= 45.0 { "Large text" }
+    else if lc >= 30.0 { "Spot text" }
+    else if lc >= 15.0 { "Non-text" } else { "Fail" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_values() {
+        // Reference values from the APCA 0.0.98G-4g calculator
+        let near = |a: f32, b: f32| (a - b).abs() < 0.2;
+        assert!(near(lc((0, 0, 0), (255, 255, 255)), 106.0));
+        assert!(near(lc((255, 255, 255), (0, 0, 0)), -107.9));
+        assert!(near(lc((0x88, 0x88, 0x88), (255, 255, 255)), 63.1));
+        assert_eq!(lc((0x77, 0x77, 0x77), (0x77, 0x77, 0x77)), 0.0);
+        assert_eq!(rating(-80.0), "Body");
+    }
+}
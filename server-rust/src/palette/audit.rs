@@ -0,0 +1,117 @@
+// Accessibility audit for any palette: the intended foreground/background pairs with WCAG 2 AA/AAA (normal and
+// large text) and APCA, a role-by-role contrast matrix, CVD collisions, and a jointly solved fix for both.
+use std::collections::{BTreeMap, HashSet};
+
+use serde_json::{json, Map, Value};
+
+use super::color::{self, Rgb};
+use super::solver::{self, Constraint, ContrastModel, PairContrast, CONTRAST_PAIRS};
+use super::cvd::{self, Condition};
+use super::oklch;
+
+// Surfaces stay put when fixing; foregrounds move to meet them
+const SURFACES: [&str; 3] = ["background", "backgroundDark", "primary"];
+/// Most roles accepted per request; the matrix and CVD pairs grow with the square of the role count.
+pub const MAX_ROLES: usize = 64;
+
+/// Colors by role, and the roles whose values weren't colors.
+pub type Parsed = (BTreeMap<String, Rgb>, Vec<String>);
+
+/// Parses hex and `oklch()` values; anything else is returned as invalid.
+pub fn parse_palette(raw: &Map<String, Value>) -> Parsed {
+    let mut colors = BTreeMap::new();
+    let mut invalid = Vec::new();
+    for (k, v) in raw {
+        match v.as_str().and_then(color::parse_color) {
+            Some(rgb) => { colors.insert(k.clone(), rgb); }
+            None => invalid.push(k.clone()),
+        }
+    }
+    (colors, invalid)
+}
+
+pub fn audit(palette: &BTreeMap<String, Rgb>, model: ContrastModel) -> Value {
+    let roles: Map<String, Value> = palette.iter().map(|(k, v)| (k.clone(), json!({"hex": color::to_hex(*v), "oklch": oklch::css(*v)}))).collect();
+
+    let mut pairs = Vec::new();
+    let mut constraints = Vec::new();
+    for &(fg, bg, ratio_target, lc_target) in CONTRAST_PAIRS.iter() {
+        let (Some(&f), Some(&b)) = (palette.get(fg), palette.get(bg)) else { continue };
+        let m = PairContrast::measure(fg, bg, f, b);
+        let target = if model == ContrastModel::Apca { lc_target } else { ratio_target };
+        let actual = if model == ContrastModel::Apca { m.apca.abs() as f32 } else { m.wcag2 as f32 };
+        let mut entry = json!(m);
+        entry["wcag"] = json!({
+            "aaNormal": m.wcag2 >= 4.5, "aaLarge": m.wcag2 >= 3.0, "aaaNormal": m.wcag2 >= 7.0, "aaaLarge": m.wcag2 >= 4.5,
+        });
+        entry["target"] = json!(target);
+        entry["pass"] = json!(actual >= target);
+        pairs.push(entry);
+        constraints.push(Constraint::Contrast { fg, bg, min: target, model });
+    }
+
+    let mut matrix = Map::new();
+    for (fg, &f) in palette {
+        let row: Map<String, Value> = palette.iter().filter(|(bg, _)| *bg != fg).map(|(bg, &b)| {
+            (bg.clone(), json!({"wcag2": (color::contrast_ratio(f, b) as f64 * 100.0).round() / 100.0, "apca": (super::apca::lc(f, b) as f64 * 10.0).round() / 10.0}))
+        }).collect();
+        matrix.insert(fg.clone(), Value::Object(row));
+    }
+
+    let colors: Vec<(String, Rgb)> = palette.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    let names: Vec<&str> = palette.keys().map(|k| k.as_str()).collect();
+    let cvd_pairs = cvd::pairs(&names);
+    let collisions = cvd::collisions(&colors, &cvd_pairs, &Condition::ALL);
+    // Only pairs that actually collide are pushed apart; roles that are meant to look alike stay alike
+    for c in &collisions {
+        let Some(&(a, b)) = cvd_pairs.iter().find(|(a, b)| *a == c.a && *b == c.b) else { continue };
+        constraints.push(Constraint::Distinct { a, b, condition: c.condition, min: cvd::COLLISION_DELTA_E });
+    }
+
+    let mut fixed_palette = palette.clone();
+    let fixed: HashSet<String> = SURFACES.iter().map(|s| s.to_string()).collect();
+    let fix = solver::solve(&mut fixed_palette, &fixed, &constraints);
+    let failing = pairs.iter().filter(|p| p["pass"] == json!(false)).count();
+
+    json!({
+        "contrastModel": model.as_str(),
+        "summary": {"pairs": pairs.len(), "failing": failing, "collisions": collisions.len(), "pass": failing == 0 && collisions.is_empty()},
+        "roles": roles,
+        "pairs": pairs,
+        "matrix": matrix,
+        "cvd": {"threshold": (cvd::COLLISION_DELTA_E as f64 * 1000.0).round() / 1000.0, "collisions": collisions},
+        "fixes": {
+            "changes": fix.adjusted,
+            "unresolved": fix.unmet,
+            "palette": fixed_palette.iter().map(|(k, v)| (k.clone(), json!(color::to_hex(*v)))).collect::<Map<String, Value>>(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_failures_collisions_and_fixes_them() {
+        let raw = json!({"background": "#FFFFFF", "text": "#6E6E6E", "link": "oklch(70% 0.1 250)", "primary": "#D0312D", "success": "#3C9A3C", "onPrimary": "#FFFFFF", "logo": "n/a"});
+        let (palette, invalid) = parse_palette(raw.as_object().unwrap());
+        assert_eq!(invalid, vec!["logo"]);
+        let report = audit(&palette, ContrastModel::Wcag2);
+
+        let text = report["pairs"].as_array().unwrap().iter().find(|p| p["fg"] == "text").unwrap();
+        assert_eq!(text["pass"], false);
+        assert_eq!(text["wcag"]["aaNormal"], true);
+        assert_eq!(report["matrix"]["text"]["background"]["wcag2"], text["wcag2"]);
+        // Red and green merge for deuteranopes
+        assert!(report["cvd"]["collisions"].as_array().unwrap().iter().any(|c| c["condition"] == "deuteranopia" && c["a"] == "primary" && c["b"] == "success"));
+
+        let fixed: Map<String, Value> = report["fixes"]["palette"].as_object().unwrap().clone();
+        let (fixed, _) = parse_palette(&fixed);
+        assert!(color::contrast_ratio(fixed["text"], fixed["background"]) >= 7.0);
+        assert!(color::contrast_ratio(fixed["link"], fixed["background"]) >= 4.5);
+        assert_eq!(fixed["background"], palette["background"]);
+        // The fix also separates the colliding pair (primary is a surface, so success moves)
+        assert!(oklch::delta_e(cvd::simulate(fixed["primary"], Condition::Deuteranopia), cvd::simulate(fixed["success"], Condition::Deuteranopia)) >= cvd::COLLISION_DELTA_E);
+    }
+}
@@ -0,0 +1,54 @@
+// sRGB helpers shared by palette reporting and exports: hex (and oklch()) parsing, HSL, WCAG 2 contrast.
+
+pub type Rgb = (u8, u8, u8);
+
+/// `#RRGGBB`, `RRGGBB` or `#RGB`.
+pub fn parse_hex(s: &str) -> Option<Rgb> {
+    let t = s.trim();
+    let h = t.strip_prefix('#').unwrap_or(t);
+    let h: String = match h.len() {
+        3 => h.chars().flat_map(|c| [c, c]).collect(),
+        6 => h.to_string(),
+        _ => return None,
+    };
+    let r = u8::from_str_radix(&h[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&h[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&h[4..6], 16).ok()?;
+    Some((r, g, b))
+}
+
+/// Hex or CSS `oklch()`; oklch colors outside sRGB are gamut-mapped.
+pub fn parse_color(s: &str) -> Option<Rgb> {
+    parse_hex(s).or_else(|| super::oklch::parse(s).map(super::oklch::to_rgb))
+}
+
+pub fn to_hex((r, g, b): Rgb) -> String { format!("#{:02X}{:02X}{:02X}", r, g, b) }
+
+/// (hue degrees, saturation %, lightness %)
+pub fn rgb_to_hsl((r, g, b): Rgb) -> (f32, f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    if (max - min).abs() < f32::EPSILON { return (0.0, 0.0, l * 100.0); }
+    let d = max - min;
+    let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+    let h = if max == r { (g - b) / d + if g < b { 6.0 } else { 0.0 } } else if max == g { (b - r) / d + 2.0 } else { (r - g) / d + 4.0 };
+    (h * 60.0, s * 100.0, l * 100.0)
+}
+
+pub fn relative_luminance((r, g, b): Rgb) -> f32 {
+    let lin = |c: u8| { let c = c as f32 / 255.0; if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) } };
+    0.2126 * lin(r) + 0.7152 * lin(g) + 0.0722 * lin(b)
+}
+
+pub fn contrast_ratio(a: Rgb, b: Rgb) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (hi, lo) = if la > lb { (la, lb) } else { (lb, la) };
+    (hi + 0.05) / (lo + 0.05)
+}
+
+/// Best WCAG 2 level a ratio reaches for body text ("AA Large" = large text only).
+pub fn wcag_rating(ratio: f32) -> &'static str {
+    if ratio >= 7.0 { "AAA" } else if ratio >= 4.5 { "AA" } else if ratio >= 3.0 { "AA Large" } else { "Fail" }
+}
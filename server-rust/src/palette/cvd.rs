@@ -0,0 +1,108 @@
+// Color vision deficiency simulation (Machado et al. 2009, full severity, applied in linear sRGB; achromatopsia
+// as luminance only) and role pairs that collapse under it.
+use serde::Serialize;
+
+use super::color::Rgb;
+use super::oklch;
+
+/// Simulated colors closer than this (ΔEOK) are treated as indistinguishable.
+pub const COLLISION_DELTA_E: f32 = 0.06;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Condition { Protanopia, Deuteranopia, Tritanopia, Achromatopsia }
+
+impl Condition {
+    pub const ALL: [Condition; 4] = [Condition::Protanopia, Condition::Deuteranopia, Condition::Tritanopia, Condition::Achromatopsia];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Condition::ALL.into_iter().find(|c| c.as_str().eq_ignore_ascii_case(s.trim()))
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Condition::Protanopia => "protanopia",
+            Condition::Deuteranopia => "deuteranopia",
+            Condition::Tritanopia => "tritanopia",
+            Condition::Achromatopsia => "achromatopsia",
+        }
+    }
+
+    fn matrix(self) -> Option<[[f32; 3]; 3]> {
+        match self {
+            Condition::Protanopia => Some([[0.152286, 1.052583, -0.204868], [0.114503, 0.786281, 0.099216], [-0.003882, -0.048116, 1.051998]]),
+            Condition::Deuteranopia => Some([[0.367322, 0.860646, -0.227968], [0.280085, 0.672501, 0.047413], [-0.011820, 0.042940, 0.968881]]),
+            Condition::Tritanopia => Some([[1.255528, -0.076749, -0.178779], [-0.078411, 0.930809, 0.147602], [0.004733, 0.691367, 0.303900]]),
+            Condition::Achromatopsia => None,
+        }
+    }
+}
+
+pub fn simulate(rgb: Rgb, condition: Condition) -> Rgb {
+    let lin = [oklch::to_linear(rgb.0), oklch::to_linear(rgb.1), oklch::to_linear(rgb.2)];
+    let out = match condition.matrix() {
+        Some(m) => [0, 1, 2].map(|i| m[i][0] * lin[0] + m[i][1] * lin[1] + m[i][2] * lin[2]),
+        None => [0.2126 * lin[0] + 0.7152 * lin[1] + 0.0722 * lin[2]; 3],
+    };
+    let ch = |v: f32| (oklch::from_linear(v.clamp(0.0, 1.0)) * 255.0).round() as u8;
+    (ch(out[0]), ch(out[1]), ch(out[2]))
+}
+
+// Pairs that must stay apart; brand and status roles are also checked against each other
+const PAIRS: [(&str, &str); 4] = [("link", "text"), ("linkDark", "textDark"), ("primary", "accent"), ("primary", "secondary")];
+const DISTINCT: [&str; 9] = ["primary", "secondary", "accent", "success", "warning", "error", "danger", "info", "link"];
+
+/// Role pairs whose distinctness is checked: the fixed pairs, then every pair of brand/status roles present.
+pub fn pairs(roles: &[&str]) -> Vec<(&'static str, &'static str)> {
+    let mut out: Vec<(&str, &str)> = PAIRS.iter().copied().filter(|(a, b)| roles.contains(a) && roles.contains(b)).collect();
+    let present: Vec<&'static str> = DISTINCT.iter().copied().filter(|r| roles.contains(r)).collect();
+    for (i, &a) in present.iter().enumerate() {
+        for &b in &present[i + 1..] {
+            if !out.iter().any(|&(x, y)| (x == a && y == b) || (x == b && y == a)) { out.push((a, b)); }
+        }
+    }
+    out
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct Collision { pub condition: Condition, pub a: String, pub b: String, pub deltaE: f64, pub originalDeltaE: f64 }
+
+/// Pairs that are distinguishable normally but fall under COLLISION_DELTA_E when simulated.
+pub fn collisions(colors: &[(String, Rgb)], pairs: &[(&str, &str)], conditions: &[Condition]) -> Vec<Collision> {
+    let get = |role: &str| colors.iter().find(|(k, _)| k == role).map(|(_, v)| *v);
+    let mut out = Vec::new();
+    for &condition in conditions {
+        for &(a, b) in pairs {
+            let (Some(ca), Some(cb)) = (get(a), get(b)) else { continue };
+            let original = oklch::delta_e(ca, cb);
+            let simulated = oklch::delta_e(simulate(ca, condition), simulate(cb, condition));
+            if original >= COLLISION_DELTA_E && simulated < COLLISION_DELTA_E {
+                out.push(Collision { condition, a: a.to_string(), b: b.to_string(), deltaE: round3(simulated), originalDeltaE: round3(original) });
+            }
+        }
+    }
+    out
+}
+
+fn round3(x: f32) -> f64 { (x as f64 * 1000.0).round() / 1000.0 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn red_green_collapse_for_deuteranopes_only() {
+        let (red, green) = ((0xD0, 0x31, 0x2D), (0x3C, 0x9A, 0x3C));
+        // Machado keeps white and gray neutral
+        assert_eq!(simulate((255, 255, 255), Condition::Deuteranopia), (255, 255, 255));
+        let gray = simulate((0x80, 0x40, 0x20), Condition::Achromatopsia);
+        assert!(gray.0 == gray.1 && gray.1 == gray.2);
+
+        let colors = vec![("primary".to_string(), red), ("success".to_string(), green), ("text".to_string(), (0x11, 0x11, 0x11))];
+        let found = collisions(&colors, &pairs(&["primary", "success", "text"]), &[Condition::Deuteranopia, Condition::Tritanopia]);
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].condition, found[0].a.as_str(), found[0].b.as_str()), (Condition::Deuteranopia, "primary", "success"));
+        assert_eq!(Condition::parse("Tritanopia"), Some(Condition::Tritanopia));
+    }
+}
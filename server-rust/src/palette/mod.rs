@@ -0,0 +1,7 @@
+pub mod apca;
+pub mod audit;
+pub mod color;
+pub mod cvd;
+pub mod oklch;
+pub mod scales;
+pub mod solver;
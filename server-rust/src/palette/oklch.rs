@@ -0,0 +1,121 @@
+// OKLab/OKLCH conversions (Björn Ottosson's matrices) with chroma-reduction gamut mapping back to sRGB.
+use super::color::Rgb;
+
+/// L 0–1, C ≥ 0 (sRGB tops out near 0.37), H degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch { pub l: f32, pub c: f32, pub h: f32 }
+
+pub fn to_linear(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+pub fn from_linear(c: f32) -> f32 {
+    if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
+}
+
+pub fn rgb_to_oklab((r, g, b): Rgb) -> [f32; 3] {
+    let (r, g, b) = (to_linear(r), to_linear(g), to_linear(b));
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+// Linear sRGB, unclamped (components outside 0–1 are out of gamut)
+fn oklab_to_linear([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+    [
+        4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_,
+        -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_,
+        -0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+    ]
+}
+
+pub fn to_oklch(rgb: Rgb) -> Oklch {
+    let [l, a, b] = rgb_to_oklab(rgb);
+    let c = (a * a + b * b).sqrt();
+    let h = if c < 1e-4 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) };
+    Oklch { l, c, h }
+}
+
+fn lab_of(c: Oklch) -> [f32; 3] {
+    let h = c.h.to_radians();
+    [c.l, c.c * h.cos(), c.c * h.sin()]
+}
+
+fn in_gamut(lin: [f32; 3]) -> bool { lin.iter().all(|v| (-1e-4..=1.0 + 1e-4).contains(v)) }
+
+/// Nearest sRGB color keeping lightness and hue: chroma is reduced until the color fits.
+pub fn to_rgb(c: Oklch) -> Rgb {
+    let c = Oklch { l: c.l.clamp(0.0, 1.0), c: c.c.max(0.0), h: c.h };
+    let mut lin = oklab_to_linear(lab_of(c));
+    if !in_gamut(lin) {
+        let (mut lo, mut hi) = (0.0f32, c.c);
+        for _ in 0..20 {
+            let mid = (lo + hi) / 2.0;
+            if in_gamut(oklab_to_linear(lab_of(Oklch { c: mid, ..c }))) { lo = mid; } else { hi = mid; }
+        }
+        lin = oklab_to_linear(lab_of(Oklch { c: lo, ..c }));
+    }
+    let ch = |v: f32| (from_linear(v.clamp(0.0, 1.0)) * 255.0).round() as u8;
+    (ch(lin[0]), ch(lin[1]), ch(lin[2]))
+}
+
+/// Euclidean OKLab distance (ΔEOK; ~0.02 is a just-noticeable difference).
+pub fn delta_e(a: Rgb, b: Rgb) -> f32 {
+    let (x, y) = (rgb_to_oklab(a), rgb_to_oklab(b));
+    ((x[0] - y[0]).powi(2) + (x[1] - y[1]).powi(2) + (x[2] - y[2]).powi(2)).sqrt()
+}
+
+/// Shortest angle between two hues, 0–180.
+pub fn hue_distance(a: f32, b: f32) -> f32 {
+    let d = (a - b).rem_euclid(360.0);
+    if d > 180.0 { 360.0 - d } else { d }
+}
+
+/// CSS `oklch(L C H)` with L as 0–1 or a percentage, C as a number or a percentage (100% = 0.4)
+/// and H in degrees; an alpha after `/` is ignored.
+pub fn parse(s: &str) -> Option<Oklch> {
+    let t = s.trim().to_ascii_lowercase();
+    let inner = t.strip_prefix("oklch(")?.strip_suffix(')')?;
+    let parts: Vec<&str> = inner.split('/').next()?.split(|c: char| c.is_whitespace() || c == ',').filter(|p| !p.is_empty()).collect();
+    let [l, c, h] = parts[..] else { return None };
+    let num = |v: &str, percent_of: f32| -> Option<f32> {
+        match v.strip_suffix('%') { Some(p) => Some(p.parse::<f32>().ok()? / 100.0 * percent_of), None => v.parse().ok() }
+    };
+    let h = if h == "none" { 0.0 } else { h.strip_suffix("deg").unwrap_or(h).parse::<f32>().ok()? };
+    Some(Oklch { l: num(l, 1.0)?.clamp(0.0, 1.0), c: num(c, 0.4)?.max(0.0), h: h.rem_euclid(360.0) })
+}
+
+/// `oklch(62.3% 0.142 257.8)`
+pub fn format(c: Oklch) -> String {
+    let h = if c.c < 5e-4 { 0.0 } else { c.h };
+    format!("oklch({:.1}% {:.3} {:.1})", c.l * 100.0, c.c, h)
+}
+
+pub fn css(rgb: Rgb) -> String { format(to_oklch(rgb)) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_css_and_gamut_maps() {
+        assert_eq!(css((255, 0, 0)), "oklch(62.8% 0.258 29.2)");
+        // The printed precision is within one 8-bit step
+        let near_red = |s: &str| parse(s).map(to_rgb).is_some_and(|c| delta_e(c, (255, 0, 0)) < 0.005);
+        assert!(near_red("oklch(62.8% 0.258 29.2)"));
+        assert!(near_red("OKLCH(0.628 64.5% 29.2deg / 50%)"));
+        assert_eq!(parse("oklch(50% 0.1)"), None);
+        // Far outside sRGB: chroma is reduced, lightness and hue survive
+        let mapped = to_oklch(to_rgb(Oklch { l: 0.7, c: 0.4, h: 150.0 }));
+        assert!((mapped.l - 0.7).abs() < 0.01 && hue_distance(mapped.h, 150.0) < 2.0 && mapped.c < 0.25);
+    }
+}
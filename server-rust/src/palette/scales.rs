@@ -0,0 +1,90 @@
+// Tonal scales (50–950) for palette roles: even OKLCH lightness steps at the role's hue, with the given color
+// placed exactly at the step nearest its own lightness and chroma tapering toward the light and dark ends.
+use serde::Serialize;
+
+use super::color::{self, Rgb};
+use super::oklch::{self, Oklch};
+
+pub const STEPS: [u16; 11] = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950];
+// Reference lightness per step; the anchor bends the ramp toward its own lightness
+const LIGHTNESS: [f32; 11] = [0.97, 0.93, 0.87, 0.79, 0.71, 0.63, 0.55, 0.47, 0.39, 0.31, 0.23];
+// Ends the ramp approaches but never reaches (pure white/black)
+const L_MAX: f32 = 0.985;
+const L_MIN: f32 = 0.16;
+// Share of the anchor's chroma dropped at the far ends
+const CHROMA_TAPER: f32 = 0.7;
+/// Roles that get a scale when present.
+pub const ROLES: [&str; 5] = ["primary", "secondary", "accent", "neutralLight", "neutralDark"];
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct Step { pub step: u16, pub hex: String, pub oklch: String, pub contrastWhite: f64, pub contrastBlack: f64 }
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct Scale { pub anchorStep: u16, pub steps: Vec<Step> }
+
+/// (step, color) from 50 to 950; the anchor step is `rgb` itself.
+pub fn ramp(rgb: Rgb) -> (u16, Vec<(u16, Rgb)>) {
+    let a = oklch::to_oklch(rgb);
+    let k = (0..STEPS.len()).min_by(|&i, &j| (LIGHTNESS[i] - a.l).abs().total_cmp(&(LIGHTNESS[j] - a.l).abs())).unwrap_or(5);
+    let steps = STEPS.iter().enumerate().map(|(i, &step)| {
+        if i == k { return (step, rgb); }
+        // Linear in the reference lightness on each side of the anchor, so spacing stays even
+        let (l, t) = if i < k {
+            let l = a.l + (LIGHTNESS[i] - LIGHTNESS[k]) * (L_MAX - a.l) / (L_MAX - LIGHTNESS[k]);
+            (l, (l - a.l) / (L_MAX - a.l))
+        } else {
+            let l = a.l - (LIGHTNESS[k] - LIGHTNESS[i]) * (a.l - L_MIN) / (LIGHTNESS[k] - L_MIN);
+            (l, (a.l - l) / (a.l - L_MIN))
+        };
+        (step, oklch::to_rgb(Oklch { l, c: a.c * (1.0 - CHROMA_TAPER * t * t), h: a.h }))
+    }).collect();
+    (STEPS[k], steps)
+}
+
+pub fn scale(rgb: Rgb) -> Scale {
+    let (anchor, steps) = ramp(rgb);
+    let round2 = |x: f32| (x as f64 * 100.0).round() / 100.0;
+    let steps = steps.into_iter().map(|(step, c)| Step {
+        step,
+        hex: color::to_hex(c),
+        oklch: oklch::css(c),
+        contrastWhite: round2(color::contrast_ratio(c, (255, 255, 255))),
+        contrastBlack: round2(color::contrast_ratio(c, (0, 0, 0))),
+    }).collect();
+    Scale { anchorStep: anchor, steps }
+}
+
+/// Scales for the ROLES present in a role → color palette (hex or `oklch()`).
+pub fn for_palette(palette: &serde_json::Map<String, serde_json::Value>) -> serde_json::Map<String, serde_json::Value> {
+    palette.iter()
+        .filter(|(k, _)| ROLES.contains(&k.as_str()))
+        .filter_map(|(k, v)| Some((k.clone(), serde_json::json!(scale(color::parse_color(v.as_str()?)?)))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchors_the_color_and_steps_evenly() {
+        let blue = color::parse_hex("#2563EB").unwrap();
+        let s = scale(blue);
+        assert_eq!(s.steps.len(), 11);
+        let anchor = s.steps.iter().find(|st| st.step == s.anchorStep).unwrap();
+        assert_eq!(anchor.hex, "#2563EB");
+        // Lightness strictly falls from 50 to 950 and contrast with white rises with it
+        let ls: Vec<f32> = s.steps.iter().map(|st| oklch::to_oklch(color::parse_hex(&st.hex).unwrap()).l).collect();
+        assert!(ls.windows(2).all(|w| w[0] > w[1]), "{:?}", ls);
+        assert!(s.steps.windows(2).all(|w| w[0].contrastWhite <= w[1].contrastWhite));
+        assert!(s.steps[0].contrastBlack > 15.0 && s.steps[10].contrastWhite > 10.0);
+        // Hue holds across the ramp
+        let h = oklch::to_oklch(blue).h;
+        assert!(s.steps.iter().all(|st| oklch::hue_distance(oklch::to_oklch(color::parse_hex(&st.hex).unwrap()).h, h) < 8.0));
+
+        // A near-white neutral anchors near the top
+        assert_eq!(scale(color::parse_hex("#E5E7EB").unwrap()).anchorStep, 100);
+    }
+}
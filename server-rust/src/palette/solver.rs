@@ -0,0 +1,288 @@
+// Palette solver: role relationships are constraints, solved jointly by coordinate descent in OKLCH.
+// Each free role moves to the candidate with the least total violation, then the smallest ΔE from its
+// starting color; constraints that still fail are reported with their shortfall instead of being dropped.
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use super::apca;
+use super::cvd::{self, Condition};
+use super::color::{self, Rgb};
+use super::oklch::{self, Oklch};
+
+const ROUNDS: usize = 8;
+// Violation cost per normalized unit, against ΔE (~0.02 per noticeable step) for moving a color
+const PENALTY: f32 = 100.0;
+const NEUTRALS: [&str; 4] = ["neutralLight", "neutralDark", "neutralLightDark", "neutralDarkDark"];
+/// Foreground/background role pairs with their WCAG 2 ratio and APCA Lc targets.
+pub const CONTRAST_PAIRS: [(&str, &str, f32, f32); 9] = [
+    ("text", "background", 7.0, 75.0),
+    ("link", "background", 4.5, 60.0),
+    ("onPrimary", "primary", 4.5, 60.0),
+    ("neutralDark", "background", 4.5, 60.0),
+    ("secondary", "background", 3.0, 45.0),
+    ("accent", "background", 3.0, 45.0),
+    ("textDark", "backgroundDark", 4.5, 75.0),
+    ("linkDark", "backgroundDark", 4.5, 60.0),
+    ("neutralLightDark", "backgroundDark", 4.5, 60.0),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContrastModel { #[default] Wcag2, Apca }
+
+impl ContrastModel {
+    pub const NAMES: &'static [&'static str] = &["wcag2", "apca"];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() { "wcag2" | "wcag" => Some(Self::Wcag2), "apca" => Some(Self::Apca), _ => None }
+    }
+
+    pub fn as_str(self) -> &'static str { match self { Self::Wcag2 => "wcag2", Self::Apca => "apca" } }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Constraint {
+    /// Contrast of `fg` on `bg` at least `min` (a WCAG 2 ratio, or an APCA Lc magnitude).
+    Contrast { fg: &'static str, bg: &'static str, min: f32, model: ContrastModel },
+    /// Hue of `role` between `min` and `max` degrees from the hue of `anchor`.
+    HueRange { role: &'static str, anchor: &'static str, min: f32, max: f32 },
+    /// OKLCH chroma of `role` between `min` and `max`.
+    Chroma { role: &'static str, min: f32, max: f32 },
+    /// ΔEOK between `a` and `b`, as seen with `condition`, at least `min`.
+    Distinct { a: &'static str, b: &'static str, condition: Condition, min: f32 },
+}
+
+struct Eval { target: f32, actual: f32, shortfall: f32 }
+
+impl Constraint {
+    fn kind(&self) -> &'static str {
+        match self { Constraint::Contrast { model: ContrastModel::Apca, .. } => "apcaContrast", Constraint::Contrast { .. } => "contrast", Constraint::HueRange { .. } => "hueRange", Constraint::Chroma { .. } => "chroma", Constraint::Distinct { .. } => "cvdDistinct" }
+    }
+
+    fn roles(&self) -> Vec<&'static str> {
+        match *self {
+            Constraint::Contrast { fg, bg, .. } => vec![fg, bg],
+            Constraint::HueRange { role, anchor, .. } => vec![role, anchor],
+            Constraint::Chroma { role, .. } => vec![role],
+            Constraint::Distinct { a, b, .. } => vec![a, b],
+        }
+    }
+
+    fn condition(&self) -> Option<Condition> {
+        match *self { Constraint::Distinct { condition, .. } => Some(condition), _ => None }
+    }
+
+    // Shortfall divided by this is comparable across kinds (1 ratio point ~ 15 Lc ~ 30° ~ 0.01 chroma ~ 0.05 ΔE)
+    fn scale(&self) -> f32 {
+        match self { Constraint::Contrast { model: ContrastModel::Apca, .. } => 15.0, Constraint::Contrast { .. } => 1.0, Constraint::HueRange { .. } => 30.0, Constraint::Chroma { .. } => 0.01, Constraint::Distinct { .. } => 0.05 }
+    }
+
+    // None when a role is missing from the palette (the constraint doesn't apply)
+    fn eval(&self, get: &dyn Fn(&str) -> Option<Rgb>) -> Option<Eval> {
+        let range = |v: f32, min: f32, max: f32| {
+            if v < min { Eval { target: min, actual: v, shortfall: min - v } }
+            else if v > max { Eval { target: max, actual: v, shortfall: v - max } }
+            else { Eval { target: min, actual: v, shortfall: 0.0 } }
+        };
+        Some(match *self {
+            Constraint::Contrast { fg, bg, min, model } => {
+                let (f, b) = (get(fg)?, get(bg)?);
+                let actual = match model { ContrastModel::Wcag2 => color::contrast_ratio(f, b), ContrastModel::Apca => apca::lc(f, b).abs() };
+                Eval { target: min, actual, shortfall: (min - actual).max(0.0) }
+            }
+            Constraint::HueRange { role, anchor, min, max } => {
+                let (r, a) = (oklch::to_oklch(get(role)?), oklch::to_oklch(get(anchor)?));
+                // Hue is meaningless for near-grays; chroma constraints cover those
+                if r.c < 0.003 || a.c < 0.003 { return Some(Eval { target: min, actual: min, shortfall: 0.0 }); }
+                range(oklch::hue_distance(r.h, a.h), min, max)
+            }
+            Constraint::Chroma { role, min, max } => range(oklch::to_oklch(get(role)?).c, min, max),
+            Constraint::Distinct { a, b, condition, min } => {
+                let d = oklch::delta_e(cvd::simulate(get(a)?, condition), cvd::simulate(get(b)?, condition));
+                Eval { target: min, actual: d, shortfall: (min - d).max(0.0) }
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Unmet {
+    pub constraint: &'static str,
+    pub roles: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Condition>,
+    pub target: f64,
+    pub actual: f64,
+    pub shortfall: f64,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct Adjustment { pub role: String, pub from: String, pub to: String, pub deltaE: f64 }
+
+/// Both contrast measures for a role pair, whichever model was enforced.
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct PairContrast { pub fg: &'static str, pub bg: &'static str, pub wcag2: f64, pub wcag2Rating: &'static str, pub apca: f64, pub apcaRating: &'static str }
+
+impl PairContrast {
+    pub fn measure(fg: &'static str, bg: &'static str, f: Rgb, b: Rgb) -> Self {
+        let (ratio, lc) = (color::contrast_ratio(f, b), apca::lc(f, b));
+        PairContrast { fg, bg, wcag2: round2(ratio), wcag2Rating: color::wcag_rating(ratio), apca: round1(lc), apcaRating: apca::rating(lc) }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Report { pub unmet: Vec<Unmet>, pub adjusted: Vec<Adjustment>, pub contrast: Vec<PairContrast> }
+
+/// Brand-system rules: contrast per role pair, an analogous secondary, an accent that is neither a
+/// near-duplicate nor (unless bold) a harsh complement of primary, and neutrals tinted toward primary
+/// (plain grays allowed for conservative industries).
+pub fn brand_constraints(conservative: bool, bold: bool, model: ContrastModel) -> Vec<Constraint> {
+    let mut out: Vec<Constraint> = CONTRAST_PAIRS.iter().map(|&(fg, bg, ratio, lc)| {
+        Constraint::Contrast { fg, bg, min: if model == ContrastModel::Apca { lc } else { ratio }, model }
+    }).collect();
+    out.extend([
+        Constraint::HueRange { role: "secondary", anchor: "primary", min: 12.0, max: 36.0 },
+        Constraint::HueRange { role: "accent", anchor: "primary", min: 40.0, max: if bold { 180.0 } else { 162.0 } },
+    ]);
+    for role in NEUTRALS {
+        if conservative {
+            out.push(Constraint::Chroma { role, min: 0.0, max: 0.03 });
+        } else {
+            out.push(Constraint::Chroma { role, min: 0.005, max: 0.03 });
+            out.push(Constraint::HueRange { role, anchor: "primary", min: 0.0, max: 30.0 });
+        }
+    }
+    out
+}
+
+/// Keeps the cvd::pairs among `roles` apart under each condition.
+pub fn cvd_constraints(roles: &[&str], conditions: &[Condition]) -> Vec<Constraint> {
+    let pairs = cvd::pairs(roles);
+    conditions.iter().flat_map(|&condition| pairs.iter().map(move |&(a, b)| Constraint::Distinct { a, b, condition, min: cvd::COLLISION_DELTA_E })).collect()
+}
+
+/// Moves the non-`fixed` roles to satisfy `constraints` with as little change as possible.
+pub fn solve(palette: &mut BTreeMap<String, Rgb>, fixed: &HashSet<String>, constraints: &[Constraint]) -> Report {
+    let start = palette.clone();
+    let free: Vec<String> = palette.keys()
+        .filter(|k| !fixed.contains(*k) && constraints.iter().any(|c| c.roles().contains(&k.as_str())))
+        .cloned().collect();
+    for _ in 0..ROUNDS {
+        let mut changed = false;
+        for role in &free {
+            let own: Vec<&Constraint> = constraints.iter().filter(|c| c.roles().contains(&role.as_str())).collect();
+            let cost = |cand: Rgb| {
+                let get = |k: &str| if k == role { Some(cand) } else { palette.get(k).copied() };
+                let violation: f32 = own.iter().filter_map(|c| c.eval(&get).map(|e| e.shortfall / c.scale())).sum();
+                violation * PENALTY + oklch::delta_e(cand, start[role])
+            };
+            let current = palette[role];
+            let mut best = (cost(current), current);
+            if best.0 <= oklch::delta_e(current, start[role]) { continue; }
+            for cand in candidates(role, current, palette, &own) {
+                let c = cost(cand);
+                if c < best.0 { best = (c, cand); }
+            }
+            if best.1 != current { palette.insert(role.clone(), best.1); changed = true; }
+        }
+        if !changed { break; }
+    }
+
+    let get = |k: &str| palette.get(k).copied();
+    let unmet = constraints.iter().filter_map(|c| {
+        let e = c.eval(&get)?;
+        (e.shortfall > 1e-3).then(|| Unmet { constraint: c.kind(), roles: c.roles(), condition: c.condition(), target: round3(e.target), actual: round3(e.actual), shortfall: round3(e.shortfall) })
+    }).collect();
+    let adjusted = palette.iter().filter(|(k, v)| start[*k] != **v).map(|(k, v)| Adjustment {
+        role: k.clone(), from: color::to_hex(start[k]), to: color::to_hex(*v), deltaE: round3(oklch::delta_e(start[k], *v)),
+    }).collect();
+    let mut contrast: Vec<PairContrast> = Vec::new();
+    for c in constraints {
+        let Constraint::Contrast { fg, bg, .. } = *c else { continue };
+        let (Some(f), Some(b)) = (get(fg), get(bg)) else { continue };
+        if !contrast.iter().any(|p| p.fg == fg && p.bg == bg) { contrast.push(PairContrast::measure(fg, bg, f, b)); }
+    }
+    Report { unmet, adjusted, contrast }
+}
+
+/// `solve` over a JSON palette (role → hex); roles that aren't hex strings are left alone.
+pub fn apply(map: &mut Map<String, Value>, fixed: &HashSet<String>, constraints: &[Constraint]) -> Report {
+    let mut palette: BTreeMap<String, Rgb> = map.iter().filter_map(|(k, v)| Some((k.clone(), color::parse_color(v.as_str()?)?))).collect();
+    let report = solve(&mut palette, fixed, constraints);
+    for a in &report.adjusted { map.insert(a.role.clone(), json!(color::to_hex(palette[&a.role]))); }
+    report
+}
+
+// Lightness sweep at the current hue/chroma plus the hue and chroma bounds of the role's own constraints
+// (just inside them, so rounding to 8-bit channels doesn't land outside)
+fn candidates(role: &str, current: Rgb, palette: &BTreeMap<String, Rgb>, own: &[&Constraint]) -> Vec<Rgb> {
+    let cur = oklch::to_oklch(current);
+    let mut hues = vec![cur.h];
+    let mut chromas = vec![cur.c];
+    for c in own {
+        match **c {
+            Constraint::HueRange { role: r, anchor, min, max } if r == role => {
+                let Some(a) = palette.get(anchor) else { continue };
+                let ah = oklch::to_oklch(*a).h;
+                for d in [min + 1.0, (min + max) / 2.0, (max - 1.0).max(min)] { hues.extend([(ah + d).rem_euclid(360.0), (ah - d).rem_euclid(360.0)]); }
+            }
+            Constraint::Chroma { role: r, min, max } if r == role => {
+                chromas.extend([cur.c.clamp(min + 0.002, (max - 0.002).max(min)), (min + max) / 2.0]);
+            }
+            _ => {}
+        }
+    }
+    let mut out = Vec::new();
+    for &h in &hues {
+        for &c in &chromas {
+            for i in 0..=100 {
+                let rgb = oklch::to_rgb(Oklch { l: i as f32 / 100.0, c, h });
+                if !out.contains(&rgb) { out.push(rgb); }
+            }
+        }
+    }
+    out
+}
+
+// f64 so reports serialize without f32 noise
+fn round1(x: f32) -> f64 { (x as f64 * 10.0).round() / 10.0 }
+fn round2(x: f32) -> f64 { (x as f64 * 100.0).round() / 100.0 }
+fn round3(x: f32) -> f64 { (x as f64 * 1000.0).round() / 1000.0 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meets_constraints_or_reports_shortfall() {
+        let hex = |s: &str| color::parse_hex(s).unwrap();
+        let mut palette: BTreeMap<String, Rgb> = [
+            ("primary", "#2255CC"), ("background", "#FFFFFF"), ("link", "#88AAFF"), ("secondary", "#CC3322"),
+            ("accent", "#2A5BD0"), ("neutralLight", "#F5F5F5"), ("text", "#999999"),
+        ].into_iter().map(|(k, v)| (k.to_string(), hex(v))).collect();
+        let fixed: HashSet<String> = ["primary", "background", "text"].into_iter().map(String::from).collect();
+        let report = solve(&mut palette, &fixed, &brand_constraints(false, false, ContrastModel::Wcag2));
+
+        let get = |k: &str| palette.get(k).copied();
+        for c in brand_constraints(false, false, ContrastModel::Wcag2) {
+            let Some(e) = c.eval(&get) else { continue };
+            if c.roles().contains(&"text") { assert!(e.shortfall > 4.0); } else { assert!(e.shortfall < 1e-3, "{:?}", c); }
+        }
+        // The fixed text color can't move, so its contrast shortfall is reported
+        assert_eq!(report.unmet.len(), 1);
+        assert_eq!(report.unmet[0].roles, vec!["text", "background"]);
+        assert_eq!(palette["text"], hex("#999999"));
+        assert!(report.adjusted.iter().any(|a| a.role == "link") && report.adjusted.iter().any(|a| a.role == "neutralLight"));
+        assert_eq!(report.contrast.len(), 4);
+
+        // Enforcing APCA instead lifts the link to Lc 60
+        let mut apca_palette = palette.clone();
+        solve(&mut apca_palette, &fixed, &brand_constraints(false, false, ContrastModel::Apca));
+        assert!(apca::lc(apca_palette["link"], apca_palette["background"]) >= 60.0);
+        // Small moves: the link keeps its blue hue
+        assert!(oklch::hue_distance(oklch::to_oklch(palette["link"]).h, oklch::to_oklch(hex("#88AAFF")).h) < 5.0);
+    }
+}
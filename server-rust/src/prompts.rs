@@ -10,6 +10,48 @@ pub const BANNED: &[&str] = &[
   "lighthouse",
 ];
 
+// French counterparts of BANNED, applied when French copy is rewritten or checked
+pub const BANNED_FR: &[&str] = &[
+  "synergie","levier","révolutionnaire","disruptif","innovant",
+  "de pointe","de classe mondiale","sans friction","robuste","nouvelle génération",
+  "changement de paradigme","autonomiser","optimiser","solution clé en main",
+  "écosystème","incontournable","à valeur ajoutée","sur mesure",
+];
+
+/// Built-in banned list for a language (primary subtag); English for anything without its own list.
+pub fn builtin_banned(lang: &str) -> &'static [&'static str] {
+    match crate::text::lang::primary(lang).as_str() {
+        "fr" => BANNED_FR,
+        _ => BANNED,
+    }
+}
+
+/// Built-in list for `lang` merged with guide-specific terms (case-insensitive dedupe, built-in order first).
+pub fn banned_terms(lang: &str, extra: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = builtin_banned(lang).iter().map(|s| s.to_string()).collect();
+    for t in extra {
+        let t = t.trim();
+        if !t.is_empty() && !out.iter().any(|o| o.eq_ignore_ascii_case(t)) { out.push(t.to_string()); }
+    }
+    out
+}
+
+/// "say \"members\" (not \"customers\")" list, or empty when the guide has no preferences.
+pub fn preferred_vocabulary(pref: &[crate::models::PreferredTerm]) -> String {
+    pref.iter()
+        .filter(|p| !p.avoid.trim().is_empty() && !p.prefer.trim().is_empty())
+        .map(|p| format!("say \"{}\" (not \"{}\")", p.prefer.trim(), p.avoid.trim()))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn vocabulary_lines(guide: &crate::models::BrandGuide, lang: &str) -> String {
+    let mut out = format!("\n- **Banned Terms:** {}", banned_terms(lang, &guide.bannedTerms).join(", "));
+    let pref = preferred_vocabulary(&guide.preferredTerms);
+    if !pref.is_empty() { out.push_str(&format!("\n- **Preferred Vocabulary:** {}", pref)); }
+    out
+}
+
 #[allow(dead_code)]
 pub fn build_guide_prompt(inputs: &crate::models::UserInputs) -> String {
     let tagline_prompt = if inputs.hasExistingTagline.unwrap_or(false) {
@@ -31,7 +73,8 @@ pub fn build_guide_prompt(inputs: &crate::models::UserInputs) -> String {
     )
 }
 
-pub fn build_rewrite_system(guide: &crate::models::BrandGuide, opts: Option<&crate::models::RewriteOptions>) -> String {
+/// `lang` is the language the rewrite is written in; it selects the built-in banned list.
+pub fn build_rewrite_system(guide: &crate::models::BrandGuide, opts: Option<&crate::models::RewriteOptions>, lang: &str) -> String {
     let mut directives = String::new();
     if let Some(o) = opts {
         if let Some(level) = o.aggressiveness { // 1..5
@@ -46,15 +89,25 @@ pub fn build_rewrite_system(guide: &crate::models::BrandGuide, opts: Option<&cra
         } else {
             directives.push_str("\n- Light rewrite: adjust tone and clarity; keep most phrasing.");
         }
-        if o.keepLength.unwrap_or(true) { directives.push_str("\n- Keep similar overall length."); }
-        if o.preserveStructure.unwrap_or(true) { directives.push_str("\n- Preserve structure (lists, headings, formatting)."); }
+        // A channel target replaces the length/structure defaults with its own limits
+        let channel = crate::channels::for_options(Some(o));
+        if o.keepLength.unwrap_or(channel.is_none()) { directives.push_str("\n- Keep similar overall length."); }
+        if o.preserveStructure.unwrap_or(channel.is_none()) { directives.push_str("\n- Preserve structure (lists, headings, formatting)."); }
+        if let Some(p) = channel { directives.push_str(&crate::channels::directive(p)); }
+        match o.targetLocale.as_deref().filter(|l| !l.trim().is_empty()) {
+            Some(loc) => {
+                let lang = crate::text::lang::name(loc);
+                directives.push_str(&format!("\n- Translate and adapt the text into {} ({}). Write as a native {} copywriter in this brand's voice: adapt idioms, units, dates and punctuation conventions instead of translating word for word.", lang, loc.trim(), lang));
+            }
+            None => directives.push_str("\n- Keep the text in its original language."),
+        }
         if let Some(notes) = &o.notes { if !notes.trim().is_empty() { directives.push_str(&format!("\n- Additional guidance: {}", notes)); } }
     } else {
-        directives.push_str("\n- Light rewrite: adjust tone; keep length and structure similar.");
+        directives.push_str("\n- Light rewrite: adjust tone; keep length and structure similar.\n- Keep the text in its original language.");
     }
 
     format!(
-        "You are a brand voice expert for the brand \"{}\". Your task is to rewrite the given text to match the brand's defined voice and style.{}\n\n**Brand Guide for {}:**\n- **Industry:** {}\n- **Mission:** {}\n- **Audience:** {}\n- **Key Tone Traits:** {}\n- **Tone Description:** {}\n- **DO:** {}\n- **DON'T:** {}\n\nRewrite the following text:",
+        "You are a brand voice expert for the brand \"{}\". Your task is to rewrite the given text to match the brand's defined voice and style.{}\n\n**Brand Guide for {}:**\n- **Industry:** {}\n- **Mission:** {}\n- **Audience:** {}\n- **Key Tone Traits:** {}\n- **Tone Description:** {}\n- **DO:** {}\n- **DON'T:** {}{}\n\nRewrite the following text:",
         guide.brandName,
         directives,
         guide.brandName,
@@ -64,13 +117,20 @@ pub fn build_rewrite_system(guide: &crate::models::BrandGuide, opts: Option<&cra
         guide.tone.traits.join(", "),
         guide.tone.description,
         guide.tone.dosAndDonts.dos.join(", "),
-        guide.tone.dosAndDonts.donts.join(", ")
+        guide.tone.dosAndDonts.donts.join(", "),
+        vocabulary_lines(guide, lang)
     )
 }
 
-pub fn build_consistency_prompt(text: &str, guide: &crate::models::BrandGuide) -> String {
+/// Tells the consistency analyzer which language the text is in, so it applies that language's voice rules.
+pub fn language_line(lang: &str) -> String {
+    let name = crate::text::lang::name(lang);
+    format!("\nThe text is written in {}. Judge it as {} copy against the brand voice and any {} notes above; do not penalize it for not being in English.\n", name, name, name)
+}
+
+pub fn build_consistency_prompt(text: &str, guide: &crate::models::BrandGuide, lang: &str) -> String {
     format!(
-        "You are a brand consistency analyzer for \"{}\". Your task is to analyze the provided text and score its alignment with the brand's style guide.\n\n**Brand Guide for {}:**\n- **Industry:** {}\n- **Mission:** {}\n- **Audience:** {}\n- **Key Tone Traits:** {}\n- **Tone Description:** {}\n- **Dos:** {}\n- **Don'ts:** {}\n\n**Text to Analyze:**\n\"{}\"\n\nPlease provide a score from 0-100, a brief feedback paragraph, and a few actionable suggestions for improvement. Structure your response according to the provided JSON schema.\n",
+        "You are a brand consistency analyzer for \"{}\". Your task is to analyze the provided text and score its alignment with the brand's style guide.\n\n**Brand Guide for {}:**\n- **Industry:** {}\n- **Mission:** {}\n- **Audience:** {}\n- **Key Tone Traits:** {}\n- **Tone Description:** {}\n- **Dos:** {}\n- **Don'ts:** {}{}\n\n**Text to Analyze:**\n\"{}\"\n\nPlease provide a score from 0-100, a brief feedback paragraph, and a few actionable suggestions for improvement. Structure your response according to the provided JSON schema.\n",
         guide.brandName,
         guide.brandName,
         guide.industry,
@@ -80,6 +140,7 @@ pub fn build_consistency_prompt(text: &str, guide: &crate::models::BrandGuide) -
         guide.tone.description,
         guide.tone.dosAndDonts.dos.join("; "),
         guide.tone.dosAndDonts.donts.join("; "),
+        vocabulary_lines(guide, lang),
         text
     )
 }
@@ -183,6 +244,7 @@ use crate::models::*;
     #[test]
     fn prompt_contains_key_fields() {
         let guide = BrandGuide {
+            id: None,
             brandName: "Acme".into(),
             industry: "Technology".into(),
             logoUrl: None,
@@ -200,11 +262,27 @@ use crate::models::*;
                 m.insert("neutralDark".into(), "#111".into());
                 m
             },
+            bannedTerms: vec!["guarantee".into()],
+            preferredTerms: vec![PreferredTerm { avoid: "customers".into(), prefer: "members".into() }],
+            readability: None,
+            locales: vec![],
+            fontPairings: vec![],
+            exemplars: vec![],
         };
-        let p = build_consistency_prompt("hello", &guide);
+        let p = build_consistency_prompt("hello", &guide, "en");
         assert!(p.contains("Acme"));
         assert!(p.contains("hello"));
         assert!(p.contains("Professional"));
+        assert!(p.contains("guarantee"));
+        assert!(p.contains("say \"members\" (not \"customers\")"));
+    }
+
+    #[test]
+    fn banned_list_follows_the_language() {
+        let fr = banned_terms("fr-CA", &["guarantee".into()]);
+        assert!(fr.iter().any(|t| t == "solution clé en main") && fr.iter().any(|t| t == "guarantee"));
+        assert!(!fr.iter().any(|t| t == "solution" || t == "robust" || t == "dynamic"));
+        assert!(banned_terms("de", &[]).iter().any(|t| t == "robust"));
     }
 }
 
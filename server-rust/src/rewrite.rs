@@ -0,0 +1,251 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+use crate::{adapters::AdapterDyn, exemplars, models::{RewriteOptions, RewriteRequest}, prompts, text::protect};
+
+#[path = "rewrite/chunked.rs"]
+mod chunked;
+pub use chunked::rewrite_chunked;
+
+const MAX_VARIANTS: u32 = 5;
+const TEMP_RANGE: (f32, f32) = (0.4, 1.0);
+// Each deterministic lint error costs this many points when ranking variants
+const LINT_ERROR_PENALTY: i64 = 5;
+// keepLength: rewritten length must stay within ±20% of the original unless the request says otherwise
+const DEFAULT_LENGTH_TOLERANCE: f32 = 0.2;
+// Translations legitimately grow or shrink (French runs ~15–25% longer than English)
+const TRANSLATION_LENGTH_TOLERANCE: f32 = 0.35;
+const DEFAULT_MAX_RETRIES: u32 = 1;
+// Each retry is another model call, so the request can't ask for more than this
+const MAX_RETRIES: u32 = 3;
+
+// Largest change ratio a rewrite should need at each aggressiveness level (1..5)
+fn allowed_change_ratio(level: i32) -> f32 {
+    match level {
+        l if l <= 1 => 0.25,
+        2 => 0.4,
+        3 => 0.55,
+        4 => 0.75,
+        _ => 1.0,
+    }
+}
+
+/// Single rewrite with code/links/placeholders masked; returns the text, any protected spans the model dropped,
+/// and the provider that wrote it.
+pub async fn rewrite_once(adapter: &AdapterDyn, req: &RewriteRequest, temperature: f32) -> Result<(String, Vec<String>, &'static str)> {
+    let lang = crate::locales::output_language(req).unwrap_or_else(|| "en".to_string());
+    let sys = format!("{}{}", prompts::build_rewrite_system(&req.brandGuide, req.options.as_ref(), &lang), exemplars::prompt_block(&req.brandGuide, &req.textToRewrite));
+    let masked = protect::protect(&req.textToRewrite);
+    generate_restored(adapter, &sys, &masked, temperature).await
+}
+
+// One model call on the masked text, with protected spans put back afterwards
+async fn generate_restored(adapter: &AdapterDyn, sys: &str, masked: &protect::Protected, temperature: f32) -> Result<(String, Vec<String>, &'static str)> {
+    let sys = if masked.spans.is_empty() { sys.to_string() } else { format!("{}{}", sys, protect::PLACEHOLDER_RULE) };
+    let (out, provider) = adapter.generate_text_from(&masked.text, Some(&sys), Some(temperature)).await?;
+    let (text, dropped) = protect::restore(&out, &masked.spans);
+    Ok((text, dropped, provider))
+}
+
+fn dropped_violation(dropped: &[String]) -> Option<String> {
+    if dropped.is_empty() { return None; }
+    Some(format!("Protected spans dropped or altered: {}", dropped.join(", ")))
+}
+
+fn length_tolerance(opts: Option<&RewriteOptions>) -> f32 {
+    let translating = opts.and_then(|o| o.targetLocale.as_deref()).map(|l| !l.trim().is_empty()).unwrap_or(false);
+    let default = if translating { TRANSLATION_LENGTH_TOLERANCE } else { DEFAULT_LENGTH_TOLERANCE };
+    opts.and_then(|o| o.lengthTolerance).filter(|t| t.is_finite() && *t >= 0.0).unwrap_or(default)
+}
+
+/// keepLength/preserveStructure violations of `rewritten` against `original` (empty when it passes).
+/// Both checks default on unless a channel is targeted, matching build_rewrite_system; channel
+/// limits are checked as well.
+pub fn validate(original: &str, rewritten: &str, opts: Option<&RewriteOptions>) -> Vec<String> {
+    use crate::text::{markdown, tokenize};
+    let mut out = Vec::new();
+    let channel = crate::channels::for_options(opts);
+    if opts.and_then(|o| o.keepLength).unwrap_or(channel.is_none()) {
+        let (a, b) = (tokenize::words(original).len(), tokenize::words(rewritten).len());
+        let tol = length_tolerance(opts);
+        if a > 0 && ((b as f32 / a as f32) - 1.0).abs() > tol {
+            out.push(format!("Length: {} words vs {} in the original; keep within ±{:.0}%", b, a, tol * 100.0));
+        }
+    }
+    if opts.and_then(|o| o.preserveStructure).unwrap_or(channel.is_none()) {
+        let (a, b) = (markdown::structure(original), markdown::structure(rewritten));
+        if a.heading_levels != b.heading_levels {
+            out.push(format!("Headings: expected levels {:?}, got {:?}", a.heading_levels, b.heading_levels));
+        }
+        if a.list_items != b.list_items {
+            out.push(format!("List items: expected {}, got {}", a.list_items, b.list_items));
+        }
+        let missing: Vec<&String> = a.links.iter().filter(|l| !b.links.contains(l)).collect();
+        if !missing.is_empty() {
+            out.push(format!("Links missing or changed: {}", missing.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")));
+        }
+        if a.code_blocks != b.code_blocks {
+            out.push(format!("Code blocks: expected {} unchanged block(s), got {}", a.code_blocks.len(), b.code_blocks.len()));
+        }
+    }
+    if let Some(p) = channel { out.extend(crate::channels::check(p, rewritten)); }
+    out
+}
+
+/// Rewrites, validates keepLength/preserveStructure, and retries with the violations spelled out.
+/// Keeps the attempt with the fewest violations and reports what is still wrong.
+pub async fn rewrite_validated(adapter: &AdapterDyn, req: &RewriteRequest, temperature: f32) -> Result<(String, Value)> {
+    validated_with_context(adapter, req, temperature, "").await
+}
+
+fn max_retries(opts: Option<&RewriteOptions>) -> u32 {
+    opts.and_then(|o| o.maxRetries).unwrap_or(DEFAULT_MAX_RETRIES).min(MAX_RETRIES)
+}
+
+// `attempts` counts the first rewrite, so max_retries = 1 allows two attempts in total
+fn should_retry(violations: &[String], attempts: u32, max_retries: u32) -> bool {
+    !violations.is_empty() && attempts <= max_retries
+}
+
+// `context` is appended to the system prompt (used for the neighbouring text of a chunk)
+async fn validated_with_context(adapter: &AdapterDyn, req: &RewriteRequest, temperature: f32, context: &str) -> Result<(String, Value)> {
+    let opts = req.options.as_ref();
+    let max_retries = max_retries(opts);
+    let lang = crate::locales::output_language(req).unwrap_or_else(|| "en".to_string());
+    let sys = format!("{}{}{}", prompts::build_rewrite_system(&req.brandGuide, opts, &lang), exemplars::prompt_block(&req.brandGuide, &req.textToRewrite), context);
+    let masked = protect::protect(&req.textToRewrite);
+    let check = |text: &str, dropped: &[String]| {
+        let mut v = validate(&req.textToRewrite, text, opts);
+        v.extend(dropped_violation(dropped));
+        v
+    };
+    let (mut best, dropped, mut provider) = generate_restored(adapter, &sys, &masked, temperature).await?;
+    let mut violations = check(&best, &dropped);
+    let mut attempts = 1;
+    while should_retry(&violations, attempts, max_retries) {
+        tracing::info!("rewrite validation failed (attempt {}): {}", attempts, violations.join("; "));
+        let corrective = format!(
+            "{}\n\nA previous rewrite failed these checks:\n- {}\nRewrite the text again and fix every issue listed.",
+            sys, violations.join("\n- ")
+        );
+        let (retry, dropped, retry_provider) = generate_restored(adapter, &corrective, &masked, temperature).await?;
+        attempts += 1;
+        let retry_violations = check(&retry, &dropped);
+        if retry_violations.len() <= violations.len() { best = retry; violations = retry_violations; provider = retry_provider; }
+    }
+    let mut report = json!({"passed": violations.is_empty(), "violations": violations, "attempts": attempts, "protectedSpans": masked.spans.len(), "provider": provider});
+    if let Some(p) = crate::channels::for_options(opts) {
+        report["channel"] = json!({"id": p.id, "maxChars": p.maxChars, "characters": best.trim().chars().count()});
+    }
+    Ok((best, report))
+}
+
+/// Word diff of original → rewrite with change metrics, checked against the requested aggressiveness/keepLength.
+pub fn diff_report(original: &str, rewritten: &str, opts: Option<&RewriteOptions>) -> Value {
+    let spans = crate::text::diff::word_diff(original, rewritten);
+    let stats = crate::text::diff::change_stats(&spans);
+    // Same default as build_rewrite_system: light rewrite, keep length
+    let level = opts.and_then(|o| o.aggressiveness).unwrap_or(2);
+    let allowed = allowed_change_ratio(level);
+    let keep_length = opts.and_then(|o| o.keepLength).unwrap_or(crate::channels::for_options(opts).is_none());
+    let tolerance = length_tolerance(opts);
+    let mut flags: Vec<String> = Vec::new();
+    if stats.changeRatio > allowed {
+        flags.push(format!("Change ratio {:.2} exceeds {:.2} allowed at aggressiveness {}", stats.changeRatio, allowed, level));
+    }
+    if let Some(ratio) = stats.lengthRatio.filter(|r| keep_length && (r - 1.0).abs() > tolerance) {
+        flags.push(format!("Length changed to {:.0}% of the original (keepLength allows ±{:.0}%)", ratio * 100.0, tolerance * 100.0));
+    }
+    json!({
+        "spans": spans,
+        "html": crate::text::diff::to_html(&spans),
+        "stats": stats,
+        "allowedChangeRatio": allowed,
+        "exceedsAllowed": !flags.is_empty(),
+        "flags": flags,
+    })
+}
+
+/// Generates `n` rewrites spread across the temperature range, scores each with the consistency
+/// pipeline, and returns them best-first with a diff against the original.
+pub async fn rewrite_variants(adapter: &AdapterDyn, req: &RewriteRequest, n: u32) -> Result<Vec<Value>> {
+    let req = &crate::locales::localize_rewrite(req);
+    let target = req.options.as_ref().and_then(|o| o.targetLocale.as_deref());
+    let n = n.clamp(1, MAX_VARIANTS);
+    let temps: Vec<f32> = (0..n)
+        .map(|i| if n == 1 { 0.6 } else { TEMP_RANGE.0 + (TEMP_RANGE.1 - TEMP_RANGE.0) * i as f32 / (n - 1) as f32 })
+        .collect();
+
+    let drafts = futures::future::join_all(temps.iter().map(|t| rewrite_once(adapter, req, *t))).await;
+    let drafts: Vec<(f32, String, Vec<String>, &str)> = temps.iter().copied().zip(drafts)
+        .filter_map(|(t, r)| match r {
+            Ok((text, dropped, provider)) => Some((t, text, dropped, provider)),
+            Err(e) => { tracing::warn!("rewrite variant at temperature {} failed: {}", t, e); None }
+        })
+        .collect();
+    if drafts.is_empty() { return Err(anyhow!("all rewrite variants failed")); }
+
+    let reports = futures::future::join_all(drafts.iter().map(|(_, text, _, _)| crate::consistency::check(adapter, text, &req.brandGuide, target))).await;
+    let mut scored: Vec<(i64, Value)> = Vec::new();
+    for ((temperature, text, dropped, provider), report) in drafts.into_iter().zip(reports) {
+        let report = report.unwrap_or_else(|e| { tracing::warn!("scoring rewrite variant failed: {}", e); json!({}) });
+        let score = report.get("score").and_then(|v| v.as_i64());
+        let lint_errors = report["lint"]["findings"].as_array()
+            .map(|f| f.iter().filter(|x| x["severity"] == "error").count())
+            .unwrap_or(0) as i64;
+        let rank_key = score.unwrap_or(0) - LINT_ERROR_PENALTY * lint_errors;
+        let diff = diff_report(&req.textToRewrite, &text, req.options.as_ref());
+        let mut violations = validate(&req.textToRewrite, &text, req.options.as_ref());
+        violations.extend(dropped_violation(&dropped));
+        scored.push((rank_key, json!({
+            "text": text,
+            "temperature": (temperature * 100.0).round() / 100.0,
+            "provider": provider,
+            "score": score,
+            "lintErrors": lint_errors,
+            "feedback": report.get("feedback").cloned().unwrap_or(json!(null)),
+            "lint": report["lint"]["counts"].clone(),
+            "readability": report["readability"]["metrics"].clone(),
+            "diff": diff,
+            "validation": {"passed": violations.is_empty(), "violations": violations},
+        })));
+    }
+    scored.sort_by_key(|s| std::cmp::Reverse(s.0));
+    Ok(scored.into_iter().enumerate().map(|(i, (_, mut v))| { v["rank"] = json!(i + 1); v }).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts(v: Value) -> RewriteOptions { serde_json::from_value(v).unwrap() }
+
+    #[test]
+    fn flags_heading_list_and_link_changes() {
+        let original = "# Plans\n\nPick one:\n\n- Basic\n- Pro\n\nSee [pricing](https://x.co/pricing) or https://x.co/faq.\n\n## Support\n";
+        assert!(validate(original, original, None).is_empty());
+
+        let rewritten = "## Plans\n\nPick one:\n\n- Basic\n- Pro\n- Team\n\nSee [pricing](https://x.co/plans) or https://x.co/faq.\n\n## Support\n";
+        let v = validate(original, rewritten, Some(&opts(json!({"keepLength": false}))));
+        assert_eq!(v, vec![
+            "Headings: expected levels [1, 2], got [2, 2]",
+            "List items: expected 2, got 3",
+            "Links missing or changed: https://x.co/pricing",
+        ]);
+        // Structure checks are off when the request says so
+        assert!(validate(original, rewritten, Some(&opts(json!({"keepLength": false, "preserveStructure": false})))).is_empty());
+    }
+
+    #[test]
+    fn retries_while_failing_up_to_the_cap() {
+        assert_eq!(max_retries(None), DEFAULT_MAX_RETRIES);
+        assert_eq!(max_retries(Some(&opts(json!({"maxRetries": 0})))), 0);
+        assert_eq!(max_retries(Some(&opts(json!({"maxRetries": 1000})))), MAX_RETRIES);
+
+        let failing = vec!["List items: expected 2, got 3".to_string()];
+        assert!(should_retry(&failing, 1, 1));
+        assert!(!should_retry(&failing, 2, 1));
+        assert!(!should_retry(&failing, 1, 0));
+        assert!(!should_retry(&[], 1, 3));
+    }
+}
@@ -0,0 +1,94 @@
+// Long-document rewrites: section/paragraph chunks rewritten with bounded concurrency, each shown the
+// neighbouring text for continuity, then stitched back together with one combined validation report.
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+use crate::{adapters::AdapterDyn, models::RewriteRequest};
+use super::{rewrite_validated, validated_with_context};
+
+// Documents above this many words are rewritten in section/paragraph chunks of roughly CHUNK_WORDS
+const CHUNK_THRESHOLD_WORDS: usize = 1200;
+const CHUNK_WORDS: usize = 600;
+const DEFAULT_CHUNK_CONCURRENCY: usize = 3;
+// Neighbouring text shown to each chunk for continuity
+const CONTEXT_CHARS: usize = 400;
+
+/// Rewrites long documents chunk by chunk (sections, then paragraphs) with bounded concurrency and
+/// stitches the results; short texts go through a single validated rewrite. When `progress` is set,
+/// a `{"type":"progress"}` event is sent as each chunk finishes.
+pub async fn rewrite_chunked(adapter: &AdapterDyn, req: &RewriteRequest, temperature: f32, progress: Option<&tokio::sync::mpsc::UnboundedSender<String>>) -> Result<(String, Value)> {
+    use futures::StreamExt;
+    use crate::text::{chunk, tokenize};
+    let req = &crate::locales::localize_rewrite(req);
+    // Channel targets condense the whole text into one piece, so they are never chunked
+    let chunkable = crate::channels::for_options(req.options.as_ref()).is_none();
+    let chunks = if chunkable && tokenize::words(&req.textToRewrite).len() > CHUNK_THRESHOLD_WORDS {
+        chunk::split(&req.textToRewrite, CHUNK_WORDS)
+    } else {
+        Vec::new()
+    };
+    if chunks.len() <= 1 {
+        let out = rewrite_validated(adapter, req, temperature).await?;
+        if let Some(tx) = progress { let _ = tx.send(json!({"type":"progress","data":{"done":1,"total":1}}).to_string()); }
+        return Ok(out);
+    }
+
+    let total = chunks.len();
+    let concurrency: usize = std::env::var("REWRITE_CHUNK_CONCURRENCY").ok().and_then(|s| s.parse().ok()).filter(|n| *n > 0).unwrap_or(DEFAULT_CHUNK_CONCURRENCY);
+    tracing::info!("rewrite: {} chunks, concurrency {}", total, concurrency);
+    let jobs = chunks.iter().enumerate().map(|(i, c)| {
+        let part = RewriteRequest { textToRewrite: c.body.clone(), ..req.clone() };
+        let before = if i > 0 { tail_chars(&chunks[i - 1].body, CONTEXT_CHARS) } else { "" };
+        let after = chunks.get(i + 1).map(|n| head_chars(&n.body, CONTEXT_CHARS)).unwrap_or("");
+        let context = chunk_context(i, total, before, after);
+        async move {
+            if c.body.trim().is_empty() { return (i, Ok((c.body.clone(), json!({"passed": true, "violations": [], "attempts": 0, "protectedSpans": 0})))); }
+            (i, validated_with_context(adapter, &part, temperature, &context).await)
+        }
+    }).collect::<Vec<_>>();
+    let mut results: Vec<Option<(String, Value)>> = vec![None; total];
+    let mut done = 0;
+    let mut stream = futures::stream::iter(jobs).buffer_unordered(concurrency);
+    while let Some((i, r)) = stream.next().await {
+        results[i] = Some(r.map_err(|e| anyhow!("chunk {} of {} failed: {}", i + 1, total, e))?);
+        done += 1;
+        if let Some(tx) = progress { let _ = tx.send(json!({"type":"progress","data":{"done":done,"total":total,"chunk":i + 1}}).to_string()); }
+    }
+
+    // Stitch in order, keeping the original separators between chunks
+    let mut text = String::new();
+    let (mut violations, mut attempts, mut spans) = (Vec::<String>::new(), 0, 0);
+    let mut providers: Vec<String> = Vec::new();
+    for (i, (c, r)) in chunks.iter().zip(results).enumerate() {
+        let (body, v) = r.unwrap_or_default();
+        text.push_str(body.trim_end());
+        text.push_str(&c.sep);
+        for msg in v["violations"].as_array().into_iter().flatten().filter_map(|m| m.as_str()) {
+            violations.push(format!("Chunk {}: {}", i + 1, msg));
+        }
+        attempts += v["attempts"].as_u64().unwrap_or(0);
+        spans += v["protectedSpans"].as_u64().unwrap_or(0);
+        if let Some(p) = v["provider"].as_str().filter(|p| !providers.iter().any(|q| q == p)) { providers.push(p.to_string()); }
+    }
+    // Chunks can fall back to different providers; all of them are named
+    providers.sort();
+    let provider = if providers.is_empty() { json!(null) } else { json!(providers.join("+")) };
+    Ok((text, json!({"passed": violations.is_empty(), "violations": violations, "attempts": attempts, "protectedSpans": spans, "chunks": total, "provider": provider})))
+}
+
+fn chunk_context(i: usize, total: usize, before: &str, after: &str) -> String {
+    let mut s = format!("\n\nThe text is part {} of {} of a longer document. Rewrite only this part; do not repeat, summarize or continue the surrounding text.", i + 1, total);
+    if !before.is_empty() { s.push_str(&format!("\nFor continuity, the previous part ends with:\n\"\"\"{}\"\"\"", before)); }
+    if !after.is_empty() { s.push_str(&format!("\nThe next part begins with:\n\"\"\"{}\"\"\"", after)); }
+    s
+}
+
+fn tail_chars(s: &str, n: usize) -> &str {
+    let start = s.char_indices().rev().nth(n.saturating_sub(1)).map(|(i, _)| i).unwrap_or(0);
+    &s[start..]
+}
+
+fn head_chars(s: &str, n: usize) -> &str {
+    let end = s.char_indices().nth(n).map(|(i, _)| i).unwrap_or(s.len());
+    &s[..end]
+}
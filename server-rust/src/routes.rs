@@ -1,12 +1,19 @@
 use axum::{Json, extract::{State, Query}};
 use serde_json::json;
-use crate::{AppState, models::{GenerateGuideRequest, RewriteRequest, ConsistencyRequest, UserInputs}, prompts};
+use crate::{AppState, models::{BrandGuide, GenerateGuideRequest, RewriteRequest, ConsistencyRequest, LintRequest, UserInputs, VoiceDeriveRequest}};
 use crate::agents::orchestrator as orchestration;
 use tokio::time::{timeout, Duration};
 use axum::http::StatusCode;
 use axum::extract::ws::{WebSocketUpgrade, Message, WebSocket};
 use axum::response::Response;
 
+#[path = "routes/guides.rs"]
+pub mod guides;
+#[path = "routes/palette.rs"]
+pub mod palette;
+use guides::{guide_not_found, stored_guide};
+use palette::{contrast_model, cvd_conditions};
+
 pub async fn health() -> Json<serde_json::Value> {
     tracing::info!("health: ok");
     Json(json!({"ok": true}))
@@ -36,29 +43,108 @@ pub async fn generate_guide(State(state): State<AppState>, Json(payload): Json<G
         "onPrimary".to_string(),
     ];
     let mut suggested_map = derive_palette_fallback(&payload.inputs, &roles);
-    for (k, v) in payload.inputs.palette.iter() { suggested_map.insert(k.clone(), json!(v)); }
+    for (k, v) in hex_palette(&payload.inputs.palette) { suggested_map.insert(k, json!(v)); }
 
     let mut full = core;
     full["palette"] = serde_json::Value::Object(suggested_map);
     full["logoUrl"] = serde_json::to_value(&payload.inputs.logoUrl).unwrap_or(json!(null));
+    full["bannedTerms"] = json!(payload.inputs.bannedTerms);
+    full["preferredTerms"] = json!(payload.inputs.preferredTerms);
+    full["locales"] = json!(payload.inputs.locales);
+    full["id"] = json!(crate::guides::create(&state.guides, full.clone()).await);
     Ok(Json(full))
 }
 
 pub async fn rewrite_text(State(state): State<AppState>, Json(payload): Json<RewriteRequest>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     tracing::info!("rewrite_text: received request, text_len={} chars", payload.textToRewrite.len());
-    let sys = prompts::build_rewrite_system(&payload.brandGuide, payload.options.as_ref());
-    let text = state.adapter.generate_text(&payload.textToRewrite, Some(&sys), Some(0.6)).await.map_err(internal_err)?;
-    Ok(Json(json!({"text": text})))
+    check_channel(payload.options.as_ref())?;
+    let n = payload.options.as_ref().and_then(|o| o.variants).unwrap_or(1);
+    if n > 1 {
+        let mut variants = crate::rewrite::rewrite_variants(&*state.adapter, &payload, n).await.map_err(internal_err)?;
+        for v in variants.iter_mut() {
+            let model = model_label(&state, v);
+            v["id"] = json!(crate::feedback::record(&state.feedback, &payload, v["text"].as_str().unwrap_or(""), &model).await);
+        }
+        let best = variants.first().map(|v| v["text"].clone()).unwrap_or(json!(""));
+        let id = variants.first().map(|v| v["id"].clone()).unwrap_or(json!(null));
+        return Ok(Json(json!({"id": id, "text": best, "variants": variants})));
+    }
+    let (text, validation) = crate::rewrite::rewrite_chunked(&*state.adapter, &payload, 0.6, None).await.map_err(internal_err)?;
+    let id = crate::feedback::record(&state.feedback, &payload, &text, &model_label(&state, &validation)).await;
+    let mut out = json!({"id": id, "text": text, "validation": validation});
+    if payload.options.as_ref().and_then(|o| o.diff).unwrap_or(false) {
+        out["diff"] = crate::rewrite::diff_report(&payload.textToRewrite, &text, payload.options.as_ref());
+    }
+    Ok(Json(out))
+}
+
+// Provider that actually wrote the rewrite (from its validation report or variant), for feedback stats
+fn model_label(state: &AppState, report: &serde_json::Value) -> String {
+    report["provider"].as_str().filter(|p| !p.is_empty()).map(str::to_string).unwrap_or_else(|| state.adapter.provider_id().to_string())
+}
+
+// Unknown channel names are a client error rather than a silent fallback to an unconstrained rewrite
+fn check_channel(opts: Option<&crate::models::RewriteOptions>) -> Result<(), (StatusCode, String)> {
+    match opts.and_then(|o| o.channel.as_deref()) {
+        Some(name) if crate::channels::profile(name).is_none() => {
+            let known: Vec<&str> = crate::channels::PROFILES.iter().map(|p| p.id).collect();
+            Err((StatusCode::BAD_REQUEST, format!("unknown channel '{}'; expected one of: {}", name, known.join(", "))))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub async fn list_channels() -> Json<serde_json::Value> {
+    Json(json!({"channels": crate::channels::PROFILES}))
 }
 
 pub async fn check_consistency(State(state): State<AppState>, Json(payload): Json<ConsistencyRequest>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     tracing::info!("check_consistency: received request, text_len={} chars", payload.textToCheck.len());
-    let prompt = prompts::build_consistency_prompt(&payload.textToCheck, &payload.brandGuide);
-let schema = crate::adapters::schemas::consistency_schema();
-    let data = state.adapter.generate_json(&prompt, Some(schema), Some(0.3)).await.map_err(internal_err)?;
+    let data = crate::consistency::check(&*state.adapter, &payload.textToCheck, &payload.brandGuide, payload.locale.as_deref()).await.map_err(internal_err)?;
     Ok(Json(data))
 }
 
+pub async fn lint_text(Json(payload): Json<LintRequest>) -> Json<serde_json::Value> {
+    tracing::info!("lint_text: received request, text_len={} chars", payload.text.len());
+    // Apply the guide's rules for the text's language (e.g. fr-CA banned terms)
+    let explicit = payload.options.as_ref().and_then(|o| o.language.as_deref());
+    let guide = payload.brandGuide.as_ref().map(|g| match crate::locales::resolve(&payload.text, g, explicit).0 {
+        Some(tag) => crate::locales::localize(g, &tag),
+        None => g.clone(),
+    });
+    let report = crate::text::lint::lint(&payload.text, guide.as_ref(), payload.options.as_ref());
+    Json(json!(report))
+}
+
+pub async fn derive_voice(State(state): State<AppState>, Json(payload): Json<VoiceDeriveRequest>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    use crate::voice::{MIN_SAMPLES, MAX_SAMPLES};
+    let samples: Vec<String> = payload.samples.iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+    if !(MIN_SAMPLES..=MAX_SAMPLES).contains(&samples.len()) {
+        return Err((StatusCode::BAD_REQUEST, format!("expected {}-{} non-empty samples, got {}", MIN_SAMPLES, MAX_SAMPLES, samples.len())));
+    }
+    let stored = match payload.guideId.as_deref() { Some(id) => Some(stored_guide(&state, id).await?), None => None };
+    let mut guide = stored.or(payload.brandGuide);
+    let (tone, mut report) = crate::voice::derive(&*state.adapter, &samples, guide.as_ref()).await.map_err(internal_err)?;
+    report["tone"] = json!(tone);
+    if let Some(g) = guide.as_mut() {
+        crate::voice::merge_tone(g, &tone);
+        report["guide"] = json!(g);
+    }
+    if let Some(id) = payload.guideId.as_deref() {
+        // Merge into the current stored copy, so changes made during the model call survive and fields
+        // beyond BrandGuide (e.g. `id`) are kept
+        let merged = crate::guides::modify(&state.guides, id, |raw| {
+            if let Ok(mut g) = serde_json::from_value::<BrandGuide>(raw.clone()) {
+                crate::voice::merge_tone(&mut g, &tone);
+                raw["tone"] = json!(g.tone);
+            }
+            raw.clone()
+        }).await.ok_or_else(|| guide_not_found(id))?;
+        report["guide"] = merged;
+    }
+    Ok(Json(report))
+}
+
 fn internal_err<E: std::fmt::Display>(e: E) -> (StatusCode, String) {
     // Log full error server-side, but do not leak upstream URLs or secrets to clients
     tracing::error!("Upstream error: {}", e);
@@ -71,15 +157,35 @@ pub struct PaletteQuery {
     pub seed: Option<u64>,
     pub preset: Option<String>,
     pub model: Option<String>,
+    // Wrap the response as {palette, constraints: {unmet, adjusted}}
+    pub report: Option<bool>,
+    // Wrap the response as {palette, oklch: {role: "oklch(…)"}}
+    pub oklch: Option<bool>,
+    // wcag2 (default) or apca: which contrast measure the solver enforces
+    #[serde(rename = "contrastModel")]
+    pub contrast_model: Option<String>,
+    // Wrap the response as {palette, scales: {role: {anchorStep, steps}}}
+    pub scales: Option<bool>,
+    // Keep brand/status role pairs distinguishable under these CVD conditions ("all" or a comma list)
+    pub cvd: Option<String>,
+}
+
+fn is_conservative(industry: &str) -> bool {
+    let industry = industry.to_lowercase();
+    ["finance","bank","banking","insurance","legal","law","enterprise","b2b","health","healthcare"].iter().any(|k| industry.contains(k))
 }
 
 #[allow(unused_variables, unused_mut)]
 pub async fn suggest_palette(
     State(state): State<AppState>,
     Query(q): Query<PaletteQuery>,
-    Json(inputs): Json<UserInputs>
+    Json(mut inputs): Json<UserInputs>
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     tracing::info!("suggest_palette: request received, brand='{}'", inputs.brandName);
+    // oklch() inputs are treated as the hex they map to
+    inputs.palette = hex_palette(&inputs.palette);
+    let contrast_model = contrast_model(q.contrast_model.as_deref())?;
+    let cvd = cvd_conditions(q.cvd.as_deref())?;
     // Determine desired roles: from query ?roles=..., otherwise from user inputs or sensible defaults
     let roles: Vec<String> = if let Some(r) = q.roles.as_ref() {
         r.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
@@ -96,7 +202,7 @@ pub async fn suggest_palette(
 
     // Cache key based on brand + roles + provided palette snapshot + seed/preset/model
     let cache_key = format!(
-        "{}|{}|{}|{}|{}|seed:{}|preset:{}|model:{}",
+        "{}|{}|{}|{}|{}|seed:{}|preset:{}|model:{}|report:{}|oklch:{}|scales:{}|contrast:{}|cvd:{}",
         inputs.brandName,
         inputs.industry,
         inputs.toneTraits.join(","),
@@ -105,6 +211,11 @@ pub async fn suggest_palette(
         seed,
         preset,
         model,
+        q.report.unwrap_or(false),
+        q.oklch.unwrap_or(false),
+        q.scales.unwrap_or(false),
+        contrast_model.as_str(),
+        cvd.iter().map(|c| c.as_str()).collect::<Vec<_>>().join(","),
     );
 
     // Fast path: cache hit
@@ -166,174 +277,39 @@ pub async fn suggest_palette(
         _ => fallback_fut1.await.unwrap_or(json!({})),
     };
 
-    // Post-process: enforce brand-system constraints and tint neutrals if the model returned generic grays
-    fn parse_hex(s: &str) -> Option<(u8,u8,u8)> {
-        let t = s.trim();
-        let h = t.strip_prefix('#').unwrap_or(t);
-        if h.len() != 6 { return None; }
-        let r = u8::from_str_radix(&h[0..2], 16).ok()?;
-        let g = u8::from_str_radix(&h[2..4], 16).ok()?;
-        let b = u8::from_str_radix(&h[4..6], 16).ok()?;
-        Some((r,g,b))
-    }
-    fn to_hex(r: u8,g: u8,b: u8) -> String { format!("#{:02x}{:02x}{:02x}", r,g,b) }
-    fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32,f32,f32) {
-        let r = r as f32 / 255.0; let g = g as f32 / 255.0; let b = b as f32 / 255.0;
-        let max = r.max(g.max(b)); let min = r.min(g.min(b));
-        let mut h = 0.0; let mut s = 0.0; let l = (max + min) / 2.0;
-        if (max - min) > 0.00001 {
-            let d = max - min;
-            s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max - min) };
-            h = if (max - r).abs() < 1e-6 { (g - b) / d + if g < b { 6.0 } else { 0.0 } }
-                else if (max - g).abs() < 1e-6 { (b - r) / d + 2.0 }
-                else { (r - g) / d + 4.0 };
-            h /= 6.0;
+    // Post-process: fill dropped roles from the deterministic base, then solve the brand-system constraints
+    // (contrast, hue relationships, neutral tint) jointly; user-provided roles and the anchors never move
+    let tone: Vec<String> = inputs.toneTraits.iter().map(|s| s.to_lowercase()).collect();
+    let is_bold = tone.iter().any(|t| ["bold","vibrant","energetic","playful","innovative","confident"].contains(&t.as_str()));
+    let mut report = crate::palette::solver::Report::default();
+    if let Some(map) = result.as_object_mut() {
+        for role in &roles {
+            if !map.contains_key(role) { if let Some(base) = base_map.get(role) { map.insert(role.clone(), json!(base)); } }
         }
-        (h * 360.0, s, l)
-    }
-    fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8,u8,u8) {
-        let h = ((h % 360.0) + 360.0) % 360.0;
-        let c = (1.0 - (2.0*l - 1.0).abs()) * s;
-        let x = c * (1.0 - (((h/60.0) % 2.0) - 1.0).abs());
-        let m = l - c/2.0;
-        let (r1,g1,b1) = if h < 60.0 {(c,x,0.0)} else if h < 120.0 {(x,c,0.0)} else if h < 180.0 {(0.0,c,x)} else if h < 240.0 {(0.0,x,c)} else if h < 300.0 {(x,0.0,c)} else {(c,0.0,x)};
-        let r = ((r1 + m) * 255.0).round().clamp(0.0, 255.0) as u8;
-        let g = ((g1 + m) * 255.0).round().clamp(0.0, 255.0) as u8;
-        let b = ((b1 + m) * 255.0).round().clamp(0.0, 255.0) as u8;
-        (r,g,b)
-    }
-    fn hsl_to_hex(h: f32, s: f32, l: f32) -> String { let (r,g,b) = hsl_to_rgb(h,s,l); to_hex(r,g,b) }
-    fn norm(x:f32) -> f32 { let mut h = x % 360.0; if h < 0.0 { h += 360.0; } h }
-    fn contrast(a: (u8,u8,u8), b: (u8,u8,u8)) -> f32 {
-        fn chan(c: u8) -> f32 { let x = c as f32 / 255.0; if x <= 0.03928 { x/12.92 } else { ((x+0.055)/1.055).powf(2.4) } }
-        let la = 0.2126*chan(a.0) + 0.7152*chan(a.1) + 0.0722*chan(a.2);
-        let lb = 0.2126*chan(b.0) + 0.7152*chan(b.1) + 0.0722*chan(b.2);
-        let (br, dr) = if la > lb { (la, lb) } else { (lb, la) };
-        (br + 0.05) / (dr + 0.05)
-    }
-    fn ensure_contrast(mut hex: String, bg_hex: &str, target: f32) -> String {
-        let mut safety = 0;
-        let bg = parse_hex(bg_hex).unwrap_or((255,255,255));
-        while safety < 12 {
-            let rgb = parse_hex(&hex).unwrap_or((0,0,0));
-            if contrast(rgb, bg) >= target { break; }
-            let (h,s,mut l) = rgb_to_hsl(rgb.0,rgb.1,rgb.2);
-            let (_,_,bl) = rgb_to_hsl(bg.0,bg.1,bg.2);
-            if bl >= 0.5 { l = (l - 0.06).max(0.0); } else { l = (l + 0.06).min(1.0); }
-            hex = hsl_to_hex(h,s,l);
-            safety += 1;
+        let mut fixed: std::collections::HashSet<String> = inputs.palette.keys().cloned().collect();
+        fixed.extend(["primary", "background", "backgroundDark"].map(String::from));
+        let mut constraints = crate::palette::solver::brand_constraints(is_conservative(&inputs.industry), is_bold, contrast_model);
+        let present: Vec<&str> = map.keys().map(|k| k.as_str()).collect();
+        constraints.extend(crate::palette::solver::cvd_constraints(&present, &cvd));
+        report = crate::palette::solver::apply(map, &fixed, &constraints);
+        if !report.unmet.is_empty() {
+            tracing::warn!("suggest_palette: {} constraint(s) unmet for '{}'", report.unmet.len(), inputs.brandName);
         }
-        hex
     }
-    fn hue_distance(a_h: f32, b_h: f32) -> f32 {
-        let mut d = (a_h - b_h).abs();
-        while d >= 360.0 { d -= 360.0; }
-        if d > 180.0 { 360.0 - d } else { d }
-    }
-    fn hue_of(hex: &str) -> Option<f32> { let (r,g,b) = parse_hex(hex)?; Some(rgb_to_hsl(r,g,b).0) }
-    fn sat_of(hex: &str) -> Option<f32> { let (r,g,b) = parse_hex(hex)?; Some(rgb_to_hsl(r,g,b).1) }
-
-    let conservative_industries = ["finance","bank","banking","insurance","legal","law","enterprise","b2b","health","healthcare"];
-    let is_conservative = conservative_industries.iter().any(|k| inputs.industry.to_lowercase().contains(k));
-
-    if let Some(map) = result.as_object_mut() {
-        // Establish primary and background context
-        let primary = inputs.palette.get("primary").cloned()
-            .or_else(|| map.get("primary").and_then(|v| v.as_str()).map(|s| s.to_string()))
-            .unwrap_or("#3366cc".to_string());
-        let background = inputs.palette.get("background").cloned()
-            .or_else(|| map.get("background").and_then(|v| v.as_str()).map(|s| s.to_string()))
-            .unwrap_or_else(|| base_map.get("background").cloned().unwrap_or("#ffffff".to_string()));
-        let primary_h = hue_of(&primary).unwrap_or(220.0);
-        let (p_h, p_s, p_l) = { let (r,g,b) = parse_hex(&primary).unwrap_or((0x33,0x66,0xcc)); rgb_to_hsl(r,g,b) };
-
-        // Neutrals: avoid pure grays unless conservative; tint toward primary
-        if !is_conservative {
-            let nL_bad = map.get("neutralLight").and_then(|v| v.as_str()).map(|s| s.to_string());
-            if let Some(nl) = nL_bad {
-                let s = sat_of(&nl).unwrap_or(0.0);
-                let too_gray = s <= 0.01 || nl.to_lowercase().starts_with("#fafafa") || nl.to_lowercase().starts_with("#f5f5f5");
-                if too_gray {
-                    let (_, _, bg_l) = { let (r,g,b) = parse_hex(&background).unwrap_or((255,255,255)); rgb_to_hsl(r,g,b) };
-                    let repl = hsl_to_hex(p_h, 0.04, (bg_l+0.06).clamp(0.85, 0.98));
-                    map.insert("neutralLight".to_string(), json!(ensure_contrast(repl, &background, 4.5)));
-                }
-            } else if let Some(base) = base_map.get("neutralLight") { map.insert("neutralLight".to_string(), json!(base)); }
-
-            let nD_bad = map.get("neutralDark").and_then(|v| v.as_str()).map(|s| s.to_string());
-            if let Some(nd) = nD_bad {
-                let s = sat_of(&nd).unwrap_or(0.0);
-                let too_gray = s <= 0.01 || nd.to_lowercase().starts_with("#333") || nd.to_lowercase().starts_with("#323232");
-                if too_gray {
-                    let (_, _, bg_l) = { let (r,g,b) = parse_hex(&background).unwrap_or((255,255,255)); rgb_to_hsl(r,g,b) };
-                    let repl = hsl_to_hex(p_h, 0.04, (bg_l-0.78).clamp(0.08, 0.2));
-                    map.insert("neutralDark".to_string(), json!(ensure_contrast(repl, &background, 4.5)));
-                }
-            } else if let Some(base) = base_map.get("neutralDark") { map.insert("neutralDark".to_string(), json!(base)); }
+    let (with_report, with_oklch, with_scales) = (q.report.unwrap_or(false), q.oklch.unwrap_or(false), q.scales.unwrap_or(false));
+    if with_report || with_oklch || with_scales {
+        let mut envelope = json!({"palette": result});
+        if with_report {
+            envelope["contrastModel"] = json!(contrast_model.as_str());
+            envelope["contrast"] = json!(report.contrast);
+            envelope["constraints"] = json!({"unmet": report.unmet, "adjusted": report.adjusted});
         }
-
-        // Accent/Secondary: ensure separation and tasteful family for green/cyan brands
-        let acc_opt = map.get("accent").and_then(|v| v.as_str()).map(|s| s.to_string());
-        if let Some(acc) = acc_opt {
-            let acc_h = hue_of(&acc).unwrap_or(primary_h);
-            let d = hue_distance(primary_h, acc_h);
-            // If too close or (for balanced/subtle) harsh complement, remap to warmer counterpoint
-            let low_sep = d < 40.0;
-            let near_complement = (d-180.0).abs() < 18.0;
-            let tone: Vec<String> = inputs.toneTraits.iter().map(|s| s.to_lowercase()).collect();
-            let is_subtle = tone.iter().any(|t| ["calm","trustworthy","minimal","professional","refined"].contains(&t.as_str()));
-            let is_bold = tone.iter().any(|t| ["bold","vibrant","energetic","playful","innovative","confident"].contains(&t.as_str()));
-            let band = |h: f32| -> &'static str {
-                let h = norm(h);
-                if h < 15.0 || h >= 345.0 { "red" }
-                else if h < 45.0 { "orange" }
-                else if h < 75.0 { "yellow" }
-                else if h < 105.0 { "warm-green" }
-                else if h < 150.0 { "green" }
-                else if h < 190.0 { "cyan" }
-                else if h < 250.0 { "blue" }
-                else if h < 275.0 { "indigo" }
-                else if h < 305.0 { "violet" }
-                else if h < 335.0 { "magenta" } else { "pink" }
-            };
-            let pb = band(primary_h);
-            if low_sep || (near_complement && (is_subtle || !is_bold)) {
-                let target_h = match pb {
-                    "green" | "cyan" => if is_bold { 30.0 } else if is_subtle { 260.0 } else { 35.0 },
-                    "blue" => 35.0,
-                    "indigo" | "violet" => 40.0,
-                    "red" | "orange" => 200.0,
-                    "yellow" => 220.0,
-                    _ => norm(primary_h + 150.0),
-                };
-                let acc_s = if is_bold { (p_s*1.1).clamp(0.45,0.85) } else if is_subtle { (p_s*0.75).clamp(0.18,0.6) } else { (p_s*0.95).clamp(0.28,0.7) };
-                let acc_l = if is_bold { (p_l+0.00).clamp(0.48,0.58) } else if is_subtle { (p_l+0.06).clamp(0.54,0.66) } else { (p_l+0.04).clamp(0.5,0.62) };
-                let mut repl = hsl_to_hex(target_h, acc_s, acc_l);
-                repl = ensure_contrast(repl, &background, 3.0);
-                map.insert("accent".to_string(), json!(repl));
-            }
-        } else if let Some(base) = base_map.get("accent") { map.insert("accent".to_string(), json!(base)); }
-
-        if let Some(sec) = map.get("secondary").and_then(|v| v.as_str()).map(|s| s.to_string()) {
-            let sec_h = hue_of(&sec).unwrap_or(primary_h);
-            let d = hue_distance(primary_h, sec_h);
-            if d < 12.0 || d > 36.0 {
-                if let Some(base) = base_map.get("secondary") { map.insert("secondary".to_string(), json!(base)); }
-                else {
-                    // recompute analog secondary
-                    let sec_offset = if primary_h >= 60.0 && primary_h <= 200.0 { -18.0 } else { 20.0 };
-                    let mut repl = hsl_to_hex(norm(primary_h + sec_offset), (p_s*0.82).clamp(0.18,0.78), (p_l+0.06).clamp(0.46,0.72));
-                    repl = ensure_contrast(repl, &background, 3.0);
-                    map.insert("secondary".to_string(), json!(repl));
-                }
-            }
-        } else if let Some(base) = base_map.get("secondary") { map.insert("secondary".to_string(), json!(base)); }
-
-        // Link: ensure AA on background
-        if let Some(l) = map.get("link").and_then(|v| v.as_str()).map(|s| s.to_string()) {
-            let fixed = ensure_contrast(l, &background, 4.5);
-            map.insert("link".to_string(), json!(fixed));
+        if with_oklch { envelope["oklch"] = oklch_palette(&envelope["palette"]); }
+        if with_scales {
+            let scales = envelope["palette"].as_object().map(crate::palette::scales::for_palette).unwrap_or_default();
+            envelope["scales"] = serde_json::Value::Object(scales);
         }
+        result = envelope;
     }
 
     // Store in cache
@@ -360,6 +336,7 @@ async fn handle_ws_session(state: AppState, socket: WebSocket) {
                 // Channel to stream events
                 let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
                 let adapter = state.adapter.clone();
+                let guides = state.guides.clone();
                 let inputs = payload.inputs;
                 // storage for user notes
                 let notes_store = std::sync::Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
@@ -384,10 +361,14 @@ async fn handle_ws_session(state: AppState, socket: WebSocket) {
                     };
                     // Merge palette like HTTP route
                     let mut suggested_map = derive_palette_fallback(&inputs, &roles);
-                    for (k, v) in inputs.palette.iter() { suggested_map.insert(k.clone(), serde_json::json!(v)); }
+                    for (k, v) in hex_palette(&inputs.palette) { suggested_map.insert(k, serde_json::json!(v)); }
                     let mut full = core.guide_core;
                     full["palette"] = serde_json::Value::Object(suggested_map);
                     full["logoUrl"] = serde_json::to_value(&inputs.logoUrl).unwrap_or(serde_json::json!(null));
+                    full["bannedTerms"] = serde_json::json!(inputs.bannedTerms);
+                    full["preferredTerms"] = serde_json::json!(inputs.preferredTerms);
+                    full["locales"] = serde_json::json!(inputs.locales);
+                    full["id"] = serde_json::json!(crate::guides::create(&guides, full.clone()).await);
                     let _ = tx_clone.send(serde_json::json!({"type":"final","data": full}).to_string());
                 });
                 // Pump server events to client (writer task)
@@ -427,116 +408,71 @@ async fn handle_ws_session(state: AppState, socket: WebSocket) {
     }
 }
 
-fn derive_palette_fallback(inputs: &UserInputs, roles: &[String]) -> serde_json::Map<String, serde_json::Value> {
-    use serde_json::Value;
-    let mut out = serde_json::Map::new();
+pub async fn ws_rewrite(State(state): State<AppState>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| handle_ws_rewrite(state, socket))
+}
 
-    // Helpers
-    fn parse_hex(s: &str) -> Option<(u8,u8,u8)> {
-        let t = s.trim();
-        let h = t.strip_prefix('#').unwrap_or(t);
-        if h.len() != 6 { return None; }
-        let r = u8::from_str_radix(&h[0..2], 16).ok()?;
-        let g = u8::from_str_radix(&h[2..4], 16).ok()?;
-        let b = u8::from_str_radix(&h[4..6], 16).ok()?;
-        Some((r,g,b))
-    }
-    fn to_hex(r: u8,g: u8,b: u8) -> String { format!("#{:02x}{:02x}{:02x}", r,g,b) }
-
-    fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32,f32,f32) {
-        let r = r as f32 / 255.0; let g = g as f32 / 255.0; let b = b as f32 / 255.0;
-        let max = r.max(g.max(b)); let min = r.min(g.min(b));
-        let mut h = 0.0; let mut s = 0.0; let l = (max + min) / 2.0;
-        if (max - min) > 0.00001 {
-            let d = max - min;
-            s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max - min) };
-            h = if (max - r).abs() < 1e-6 { (g - b) / d + if g < b { 6.0 } else { 0.0 } }
-                else if (max - g).abs() < 1e-6 { (b - r) / d + 2.0 }
-                else { (r - g) / d + 4.0 };
-            h /= 6.0;
+// Same protocol as /api/orchestrate: first message is the RewriteRequest JSON, then progress events and a final result
+async fn handle_ws_rewrite(state: AppState, socket: WebSocket) {
+    use futures::{StreamExt, SinkExt};
+    let (mut ws_tx, mut ws_rx) = socket.split();
+    let Some(Ok(Message::Text(first))) = ws_rx.next().await else {
+        let _ = ws_tx.send(Message::Text(json!({"type":"error","message":"expected first message with request json"}).to_string())).await;
+        return;
+    };
+    let payload: RewriteRequest = match serde_json::from_str(&first) {
+        Ok(p) => p,
+        Err(e) => {
+            let _ = ws_tx.send(Message::Text(json!({"type":"error","message": format!("bad request: {}", e)}).to_string())).await;
+            return;
         }
-        (h * 360.0, s, l)
-    }
-    fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8,u8,u8) {
-        let h = ((h % 360.0) + 360.0) % 360.0;
-        let c = (1.0 - (2.0*l - 1.0).abs()) * s;
-        let x = c * (1.0 - (((h/60.0) % 2.0) - 1.0).abs());
-        let m = l - c/2.0;
-        let (r1,g1,b1) = if h < 60.0 {(c,x,0.0)} else if h < 120.0 {(x,c,0.0)} else if h < 180.0 {(0.0,c,x)} else if h < 240.0 {(0.0,x,c)} else if h < 300.0 {(x,0.0,c)} else {(c,0.0,x)};
-        let r = ((r1 + m) * 255.0).round().clamp(0.0, 255.0) as u8;
-        let g = ((g1 + m) * 255.0).round().clamp(0.0, 255.0) as u8;
-        let b = ((b1 + m) * 255.0).round().clamp(0.0, 255.0) as u8;
-        (r,g,b)
-    }
-    fn hsl_to_hex(h: f32, s: f32, l: f32) -> String { let (r,g,b) = hsl_to_rgb(h,s,l); to_hex(r,g,b) }
-
-    fn rel_lum(r: u8, g: u8, b: u8) -> f32 {
-        fn chan(c: u8) -> f32 { let x = c as f32 / 255.0; if x <= 0.03928 { x/12.92 } else { ((x+0.055)/1.055).powf(2.4) } }
-        0.2126*chan(r) + 0.7152*chan(g) + 0.0722*chan(b)
+    };
+    if let Err((_, message)) = check_channel(payload.options.as_ref()) {
+        let _ = ws_tx.send(Message::Text(json!({"type":"error","message": message}).to_string())).await;
+        return;
     }
-    fn contrast(a: (u8,u8,u8), b: (u8,u8,u8)) -> f32 {
-        let la = rel_lum(a.0,a.1,a.2); let lb = rel_lum(b.0,b.1,b.2);
-        let (br, dr) = if la > lb { (la, lb) } else { (lb, la) };
-        (br + 0.05) / (dr + 0.05)
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let adapter = state.adapter.clone();
+    tokio::spawn(async move {
+        let msg = match crate::rewrite::rewrite_chunked(&*adapter, &payload, 0.6, Some(&tx)).await {
+            Ok((text, validation)) => {
+                let id = crate::feedback::record(&state.feedback, &payload, &text, &model_label(&state, &validation)).await;
+                let mut data = json!({"id": id, "text": text, "validation": validation});
+                if payload.options.as_ref().and_then(|o| o.diff).unwrap_or(false) {
+                    data["diff"] = crate::rewrite::diff_report(&payload.textToRewrite, &text, payload.options.as_ref());
+                }
+                json!({"type":"final","data": data})
+            }
+            Err(e) => json!({"type":"error","message": e.to_string()}),
+        };
+        let _ = tx.send(msg.to_string());
+    });
+    while let Some(msg) = rx.recv().await {
+        if ws_tx.send(Message::Text(msg)).await.is_err() { break; }
     }
+}
 
-    fn ensure_contrast(mut hex: String, bg_hex: &str, target: f32) -> String {
-        let mut safety = 0;
-        let bg = parse_hex(bg_hex).unwrap_or((255,255,255));
-        while safety < 12 {
-            let rgb = parse_hex(&hex).unwrap_or((0,0,0));
-            if contrast(rgb, bg) >= target { break; }
-            // If bg is light, darken; else lighten
-            let (_,_,bl) = rgb_to_hsl(bg.0, bg.1, bg.2);
-            let (h,s,mut l) = rgb_to_hsl(rgb.0,rgb.1,rgb.2);
-            if bl >= 0.5 { l = (l - 0.06).max(0.0); } else { l = (l + 0.06).min(1.0); }
-            hex = hsl_to_hex(h,s,l);
-            safety += 1;
-        }
-        hex
-    }
+fn derive_palette_fallback(inputs: &UserInputs, roles: &[String]) -> serde_json::Map<String, serde_json::Value> {
+    use serde_json::Value;
+    use crate::palette::{color, oklch::{self, Oklch}};
+    let mut out = serde_json::Map::new();
 
-    fn norm(x:f32) -> f32 { let mut h = x % 360.0; if h < 0.0 { h += 360.0; } h }
+    // All derivation happens in OKLCH so roles at the same L read as the same visual weight whatever their hue;
+    // to_rgb gamut-maps by reducing chroma
+    let hex = |l: f32, c: f32, h: f32| color::to_hex(oklch::to_rgb(Oklch { l, c, h: h.rem_euclid(360.0) }));
+    let user: std::collections::HashMap<String, String> = hex_palette(&inputs.palette);
 
     // Inputs
-    let primary_hex = inputs.palette.get("primary").cloned().unwrap_or("#3366cc".into());
-    let (pr,pg,pb) = parse_hex(&primary_hex).unwrap_or((0x33,0x66,0xcc));
-    let (p_h, p_s, p_l) = rgb_to_hsl(pr,pg,pb);
+    let primary_hex = user.get("primary").cloned().unwrap_or("#3366CC".into());
+    let p = oklch::to_oklch(color::parse_hex(&primary_hex).unwrap_or((0x33,0x66,0xcc)));
 
     // Background: near-white with subtle brand tint
-    let mut bg_hex = inputs.palette.get("background").cloned().unwrap_or_else(|| {
-        let tint_s = 0.015f32; let tint_l = 0.97f32;
-        hsl_to_hex(p_h, tint_s, tint_l)
-    });
-    // Text for background
-    let mut text_hex = inputs.palette.get("text").cloned().unwrap_or_else(|| {
-        let bg = parse_hex(&bg_hex).unwrap_or((255,255,255));
-        let c_black = contrast((0,0,0), bg);
-        let c_white = contrast((255,255,255), bg);
-        if c_black >= c_white { "#0e0f10".into() } else { "#ffffff".into() }
-    });
-    text_hex = ensure_contrast(text_hex.clone(), &bg_hex, 7.0);
-
-    // Secondary: analog hue ±12–28°, slightly different lightness
-    let sec_offset = if p_h >= 60.0 && p_h <= 200.0 { -18.0 } else { 20.0 };
-    let mut secondary_h = norm(p_h + sec_offset);
-    let mut secondary_s = (p_s * 0.82).clamp(0.18, 0.78);
-    let mut secondary_l = (p_l + 0.06).clamp(0.46, 0.72);
-
-    // Accent: tasteful counterpoint by hue family mapping
-    fn band(h: f32) -> &'static str {
-        let h = norm(h);
-        if h < 15.0 || h >= 345.0 { "red" }
-        else if h < 45.0 { "orange" }
-        else if h < 75.0 { "yellow" }
-        else if h < 105.0 { "warm-green" }
-        else if h < 150.0 { "green" }
-        else if h < 190.0 { "cyan" }
-        else if h < 250.0 { "blue" }
-        else if h < 275.0 { "indigo" }
-        else if h < 305.0 { "violet" }
-        else if h < 335.0 { "magenta" } else { "pink" }
-    }
+    let bg_hex = user.get("background").cloned().unwrap_or_else(|| hex(0.985, 0.004, p.h));
+    let bg = oklch::to_oklch(color::parse_hex(&bg_hex).unwrap_or((255,255,255)));
+    let light_bg = bg.l >= 0.6;
+    // Text for background (the solver lifts it to 7:1)
+    let text_hex = user.get("text").cloned().unwrap_or_else(|| if light_bg { hex(0.18, 0.01, p.h) } else { hex(0.97, 0.005, p.h) });
+
     let vibe = {
         let t: Vec<String> = inputs.toneTraits.iter().map(|s| s.to_lowercase()).collect();
         let bold = ["bold","vibrant","energetic","playful","innovative","confident"];
@@ -544,57 +480,41 @@ fn derive_palette_fallback(inputs: &UserInputs, roles: &[String]) -> serde_json:
         if t.iter().any(|x| bold.contains(&x.as_str())) { "bold" }
         else if t.iter().any(|x| subtle.contains(&x.as_str())) { "subtle" } else { "balanced" }
     };
-    let mut accent_h = match (band(p_h), vibe) {
-        ("green", "subtle") | ("cyan", "subtle") => 260.0, // indigo/violet
-        ("green", "bold") | ("cyan", "bold") => 30.0,       // amber
-        ("blue", _) => 35.0,                                   // saffron
-        ("indigo", _) | ("violet", _) => 40.0,                // warm gold
-        ("red", _) | ("orange", _) => 200.0,                   // teal/cyan
-        ("yellow", _) => 220.0,                                 // blue
-        _ => norm(p_h + 150.0),
-    };
-    // Tune saturation/lightness per vibe
-    let mut accent_s = match vibe { "bold" => (p_s*1.1).clamp(0.45,0.85), "subtle" => (p_s*0.75).clamp(0.18,0.6), _ => (p_s*0.95).clamp(0.28,0.7) };
-    let mut accent_l = match vibe { "bold" => (p_l+0.00).clamp(0.48,0.58), "subtle" => (p_l+0.06).clamp(0.54,0.66), _ => (p_l+0.04).clamp(0.5,0.62) };
 
-    // Neutrals: very low-sat tints of primary hue (cohesive UI)
-    let (_, _, bg_l) = { let (r,g,b) = parse_hex(&bg_hex).unwrap_or((255,255,255)); rgb_to_hsl(r,g,b) };
-    let neutral_light = hsl_to_hex(p_h, 0.02, (bg_l+0.06).clamp(0.85, 0.98));
-    let neutral_dark  = hsl_to_hex(p_h, 0.02, (bg_l-0.78).clamp(0.08, 0.2));
+    // Secondary: analogous hue ~22° away (toward blue for greens/cyans, warmer otherwise), slightly lighter
+    let sec_offset = if (130.0..230.0).contains(&p.h) { 22.0 } else { -22.0 };
+    let secondary_hex = hex((p.l + 0.05).clamp(0.5, 0.75), (p.c * 0.85).clamp(0.04, 0.2), p.h + sec_offset);
 
-    // Build role colors
-    let mut secondary_hex = hsl_to_hex(secondary_h, secondary_s, secondary_l);
-    let mut accent_hex    = hsl_to_hex(accent_h, accent_s, accent_l);
+    // Accent: a counterpoint hue at a fixed perceived lightness per vibe; the solver keeps it clear of
+    // primary and (unless bold) of the harsh complement
+    let (accent_l, accent_c) = match vibe {
+        "bold" => (0.64, (p.c * 1.15).clamp(0.12, 0.24)),
+        "subtle" => (0.70, (p.c * 0.75).clamp(0.05, 0.14)),
+        _ => (0.67, (p.c * 0.95).clamp(0.08, 0.19)),
+    };
+    let accent_hex = hex(accent_l, accent_c, p.h + if vibe == "bold" { 165.0 } else { 150.0 });
 
-    // Link: related to primary but distinct
-    let (mut link_h, mut link_s, mut link_l) = (norm(p_h - 10.0), (p_s*1.05).clamp(0.22,0.9), p_l);
-    let bg_l_for_link = bg_l;
-    if bg_l_for_link >= 0.5 { link_l = (link_l - 0.12).max(0.18); } else { link_l = (link_l + 0.12).min(0.82); }
-    let mut link_hex = hsl_to_hex(link_h, link_s, link_l);
+    // Neutrals: low-chroma tints of the primary hue (cohesive UI)
+    let neutral_light = hex((bg.l - 0.045).clamp(0.88, 0.96), 0.012, p.h);
+    let neutral_dark = hex(0.32, 0.012, p.h);
 
-    // Dark mode companions
-    let background_dark = hsl_to_hex(p_h, 0.06, 0.12);
-    let text_dark = ensure_contrast("#ffffff".to_string(), &background_dark, 4.5);
-    let mut link_dark = hsl_to_hex(norm(link_h - 4.0), (link_s*1.1).clamp(0.2,0.95), (link_l+0.18).clamp(0.32,0.9));
+    // Link: related to primary but distinct, pushed away from the background's lightness
+    let link_c = (p.c * 1.05).clamp(0.08, 0.22);
+    let link_hex = hex(if light_bg { p.l.min(0.55) - 0.05 } else { p.l.max(0.72) }, link_c, p.h - 8.0);
 
-    // Ensure contrast
-    link_hex = ensure_contrast(link_hex, &bg_hex, 4.5);
-    secondary_hex = ensure_contrast(secondary_hex, &bg_hex, 3.0); // not required text-on-bg
-    accent_hex    = ensure_contrast(accent_hex, &bg_hex, 3.0);
+    // Dark mode companions
+    let background_dark = hex(0.2, 0.015, p.h);
+    let text_dark = hex(0.96, 0.008, p.h);
+    let link_dark = hex(0.76, link_c, p.h - 12.0);
+    let neutral_light_dark = hex(0.66, 0.012, p.h);
+    let neutral_dark_dark = hex(0.26, 0.012, p.h);
 
     // onPrimary: pick white/black by contrast
     let on_primary = {
-        let prgb = parse_hex(&primary_hex).unwrap_or((0,0,0));
-        let c_black = contrast(prgb, (0,0,0));
-        let c_white = contrast(prgb, (255,255,255));
-        if c_white >= c_black { "#ffffff".to_string() } else { "#000000".to_string() }
+        let prgb = color::parse_hex(&primary_hex).unwrap_or((0,0,0));
+        if color::contrast_ratio(prgb, (255,255,255)) >= color::contrast_ratio(prgb, (0,0,0)) { "#FFFFFF".to_string() } else { "#000000".to_string() }
     };
 
-    // Dark neutrals from tinted dark background
-    let (_, _, bd_l) = { let (r,g,b) = parse_hex(&background_dark).unwrap_or((0,0,0)); rgb_to_hsl(r,g,b) };
-    let neutral_light_dark = ensure_contrast(hsl_to_hex(p_h, 0.02, (bd_l+0.20).clamp(0.20,0.4)), &background_dark, 4.5);
-    let neutral_dark_dark  = ensure_contrast(hsl_to_hex(p_h, 0.02, (bd_l-0.08).clamp(0.04,0.16)), &background_dark, 4.5);
-
     // Candidate map
     let mut candidates: std::collections::HashMap<String, String> = std::collections::HashMap::new();
     candidates.insert("primary".into(), primary_hex.clone());
@@ -612,10 +532,17 @@ fn derive_palette_fallback(inputs: &UserInputs, roles: &[String]) -> serde_json:
     candidates.insert("neutralLightDark".into(), neutral_light_dark.clone());
     candidates.insert("neutralDarkDark".into(), neutral_dark_dark.clone());
 
+    // Solve the brand-system constraints over the whole candidate set (user values fixed), so roles are
+    // checked against each other even when only some of them are requested
+    let mut solved: serde_json::Map<String, Value> = candidates.iter().map(|(k, v)| (k.clone(), Value::String(v.clone()))).collect();
+    for (k, v) in user.iter() { solved.insert(k.clone(), Value::String(v.clone())); }
+    let mut fixed: std::collections::HashSet<String> = user.keys().cloned().collect();
+    fixed.extend(["primary", "background", "backgroundDark"].map(String::from));
+    crate::palette::solver::apply(&mut solved, &fixed, &crate::palette::solver::brand_constraints(is_conservative(&inputs.industry), vibe == "bold", Default::default()));
+    for (k, v) in solved { if let Value::String(v) = v { candidates.insert(k, v); } }
+
     // Merge user-provided values first
-    for (k, v) in inputs.palette.iter() {
-        if !v.trim().is_empty() { out.insert(k.clone(), Value::String(v.clone())); }
-    }
+    for (k, v) in user.iter() { out.insert(k.clone(), Value::String(v.clone())); }
     // Then fill requested roles from candidates
     for role in roles {
         if !out.contains_key(role) {
@@ -627,3 +554,19 @@ fn derive_palette_fallback(inputs: &UserInputs, roles: &[String]) -> serde_json:
     out
 }
 
+
+// role → `oklch()` string for every hex role
+fn oklch_palette(palette: &serde_json::Value) -> serde_json::Value {
+    let map: serde_json::Map<String, serde_json::Value> = palette.as_object().into_iter().flatten()
+        .filter_map(|(k, v)| Some((k.clone(), json!(crate::palette::oklch::css(crate::palette::color::parse_hex(v.as_str()?)?)))))
+        .collect();
+    serde_json::Value::Object(map)
+}
+
+// User palette values as hex: `oklch()` inputs are converted, blanks dropped, anything else kept as given
+fn hex_palette(palette: &std::collections::HashMap<String, String>) -> std::collections::HashMap<String, String> {
+    palette.iter()
+        .filter(|(_, v)| !v.trim().is_empty())
+        .map(|(k, v)| (k.clone(), crate::palette::oklch::parse(v).map(|c| crate::palette::color::to_hex(crate::palette::oklch::to_rgb(c))).unwrap_or_else(|| v.clone())))
+        .collect()
+}
@@ -0,0 +1,154 @@
+// Stored guide endpoints: create/update/import, exemplars, exports (brand book, Markdown, DOCX, design tokens)
+// and rewrite feedback, which lands on the guide as exemplars and per-guide stats.
+use axum::{Json, extract::{State, Query, Path}};
+use axum::http::StatusCode;
+use axum::response::Response;
+use serde_json::json;
+
+use crate::{AppState, models::{BrandGuide, Exemplar}};
+
+pub async fn save_guide(State(state): State<AppState>, Json(payload): Json<serde_json::Value>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    validate_guide(&payload)?;
+    let id = crate::guides::create(&state.guides, payload).await;
+    Ok(Json(json!({"id": id})))
+}
+
+pub async fn update_guide(State(state): State<AppState>, Path(id): Path<String>, Json(payload): Json<serde_json::Value>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    validate_guide(&payload)?;
+    if !crate::guides::update(&state.guides, &id, payload).await { return Err(guide_not_found(&id)); }
+    Ok(Json(json!({"id": id})))
+}
+
+// Must be a usable BrandGuide; the stored copy keeps any extra fields the client sent
+fn validate_guide(payload: &serde_json::Value) -> Result<(), (StatusCode, String)> {
+    serde_json::from_value::<BrandGuide>(payload.clone()).map(|_| ()).map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, format!("invalid guide: {}", e)))
+}
+
+pub const IMPORT_MAX_BYTES: usize = 20 * 1024 * 1024;
+
+// Multipart upload with a `file` part (Markdown, plain text, DOCX or text-based PDF)
+pub async fn import_guide(State(state): State<AppState>, mut multipart: axum::extract::Multipart) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let bad_request = |e: axum::extract::multipart::MultipartError| (StatusCode::BAD_REQUEST, format!("invalid upload: {}", e));
+    let mut upload = None;
+    while let Some(field) = multipart.next_field().await.map_err(bad_request)? {
+        if field.name() != Some("file") && field.file_name().is_none() { continue; }
+        let (name, content_type) = (field.file_name().map(|s| s.to_string()), field.content_type().map(|s| s.to_string()));
+        upload = Some((name, content_type, field.bytes().await.map_err(bad_request)?));
+        break;
+    }
+    let (name, content_type, bytes) = upload.ok_or((StatusCode::BAD_REQUEST, "missing `file` part".to_string()))?;
+    tracing::info!("import_guide: file={:?} bytes={}", name, bytes.len());
+    let format = crate::import::extract::detect(name.as_deref(), content_type.as_deref(), &bytes)
+        .ok_or((StatusCode::UNSUPPORTED_MEDIA_TYPE, "unsupported file; upload Markdown, text, DOCX or PDF".to_string()))?;
+    let text = crate::import::extract::extract(format, &bytes).map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    Ok(Json(crate::import::import(&*state.adapter, format, &text).await))
+}
+
+pub async fn get_guide(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    crate::guides::get(&state.guides, &id).await.map(Json).ok_or_else(|| guide_not_found(&id))
+}
+
+// Body: one exemplar object or an array of them
+pub async fn add_exemplars(State(state): State<AppState>, Path(id): Path<String>, Json(payload): Json<serde_json::Value>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let items = if payload.is_array() { payload } else { json!([payload]) };
+    let pairs: Vec<Exemplar> = serde_json::from_value(items).map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, format!("invalid exemplar: {}", e)))?;
+    let (added, total) = crate::guides::modify(&state.guides, &id, |guide| {
+        let added = crate::exemplars::append(guide, pairs);
+        (added, guide["exemplars"].as_array().map(|a| a.len()).unwrap_or(0))
+    }).await.ok_or_else(|| guide_not_found(&id))?;
+    Ok(Json(json!({"id": id, "added": added, "exemplars": total})))
+}
+
+pub async fn export_guide_pdf(State(state): State<AppState>, Path(id): Path<String>) -> Result<Response, (StatusCode, String)> {
+    let guide = stored_guide(&state, &id).await?;
+    let logo = match guide.logoUrl.as_deref() { Some(u) => crate::export::image::load(u).await, None => None };
+    let pdf = crate::export::brandbook::render(&guide, logo);
+    Ok(attachment("application/pdf", &format!("{}-brand-book.pdf", slug(&guide.brandName)), pdf))
+}
+
+pub async fn export_guide_markdown(State(state): State<AppState>, Path(id): Path<String>) -> Result<Response, (StatusCode, String)> {
+    let guide = stored_guide(&state, &id).await?;
+    let md = crate::export::markdown::render(&guide);
+    Ok(attachment("text/markdown; charset=utf-8", &format!("{}-brand-guide.md", slug(&guide.brandName)), md.into_bytes()))
+}
+
+pub async fn export_guide_docx(State(state): State<AppState>, Path(id): Path<String>) -> Result<Response, (StatusCode, String)> {
+    let guide = stored_guide(&state, &id).await?;
+    let docx = crate::export::docx::render(&guide);
+    Ok(attachment("application/vnd.openxmlformats-officedocument.wordprocessingml.document", &format!("{}-brand-guide.docx", slug(&guide.brandName)), docx))
+}
+
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+pub struct TokensQuery {
+    pub format: Option<String>,
+    // Also export 50–950 tonal steps for primary, secondary, accent and the neutrals
+    pub scales: Option<bool>,
+}
+
+pub async fn export_guide_tokens(State(state): State<AppState>, Path(id): Path<String>, Query(q): Query<TokensQuery>) -> Result<Response, (StatusCode, String)> {
+    let guide = stored_guide(&state, &id).await?;
+    tokens_response(&guide.palette, &q, Some(&guide.brandName))
+}
+
+pub async fn export_tokens(Query(q): Query<TokensQuery>, Json(palette): Json<crate::models::Palette>) -> Result<Response, (StatusCode, String)> {
+    tokens_response(&palette, &q, None)
+}
+
+fn tokens_response(palette: &crate::models::Palette, q: &TokensQuery, brand: Option<&str>) -> Result<Response, (StatusCode, String)> {
+    use crate::export::tokens::{self, Format};
+    let format = match q.format.as_deref() {
+        None => Format::Dtcg,
+        Some(f) => Format::parse(f).ok_or_else(|| (StatusCode::BAD_REQUEST, format!("unknown token format '{}'; expected one of: {}", f, Format::NAMES.join(", "))))?,
+    };
+    let (content_type, filename) = format.file();
+    let filename = match brand { Some(b) => format!("{}-{}", slug(b), filename), None => filename.to_string() };
+    let mut list = tokens::from_palette(palette);
+    if q.scales.unwrap_or(false) { list = tokens::with_scales(list); }
+    Ok(attachment(content_type, &filename, tokens::render(&list, format).into_bytes()))
+}
+
+pub(crate) fn guide_not_found(id: &str) -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, format!("guide '{}' not found", id))
+}
+
+pub(crate) async fn stored_guide(state: &AppState, id: &str) -> Result<BrandGuide, (StatusCode, String)> {
+    let raw = crate::guides::get(&state.guides, id).await.ok_or_else(|| guide_not_found(id))?;
+    serde_json::from_value(raw).map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, format!("stored guide is incomplete: {}", e)))
+}
+
+fn attachment(content_type: &str, filename: &str, body: Vec<u8>) -> Response {
+    use axum::response::IntoResponse;
+    let disposition = format!("attachment; filename=\"{}\"", filename);
+    ([(axum::http::header::CONTENT_TYPE, content_type.to_string()), (axum::http::header::CONTENT_DISPOSITION, disposition)], body).into_response()
+}
+
+// Lowercase ASCII filename stem from a brand name
+fn slug(name: &str) -> String {
+    let s: String = name.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' }).collect();
+    let s = s.split('-').filter(|p| !p.is_empty()).collect::<Vec<_>>().join("-");
+    if s.is_empty() { "brand".to_string() } else { s }
+}
+
+pub async fn rewrite_feedback(State(state): State<AppState>, Path(id): Path<String>, Json(payload): Json<crate::feedback::FeedbackRequest>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    use crate::feedback::Verdict;
+    let edited = payload.editedText.as_deref().map(str::trim).filter(|t| !t.is_empty());
+    if payload.verdict == Verdict::Edit && edited.is_none() {
+        return Err((StatusCode::BAD_REQUEST, "verdict 'edit' requires editedText".to_string()));
+    }
+    let rec = crate::feedback::submit(&state.feedback, &id, &payload).await
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("rewrite '{}' not found (ids expire after a while)", id)))?;
+    // Kept rewrites become exemplars on the stored guide
+    let mut exemplar_added = false;
+    if let (true, Some(guide_id)) = (payload.verdict != Verdict::Reject, rec.guideId.as_deref()) {
+        let on_brand = edited.unwrap_or(&rec.rewritten).to_string();
+        let off_brand = Some(rec.original.clone()).filter(|o| o.trim() != on_brand.trim());
+        let source = if payload.verdict == Verdict::Edit { "rewrite:edited" } else { "rewrite:accepted" };
+        let pair = Exemplar { onBrand: on_brand, offBrand: off_brand, note: payload.comment.clone(), source: Some(source.into()) };
+        exemplar_added = crate::guides::modify(&state.guides, guide_id, |guide| crate::exemplars::append(guide, vec![pair]) > 0).await.unwrap_or(false);
+    }
+    Ok(Json(json!({"rewriteId": id, "verdict": payload.verdict, "guideId": rec.guideId, "exemplarAdded": exemplar_added})))
+}
+
+pub async fn feedback_stats(State(state): State<AppState>, Path(id): Path<String>) -> Json<serde_json::Value> {
+    Json(crate::feedback::stats(&state.feedback, &id).await)
+}
@@ -0,0 +1,79 @@
+// Palette audit and CVD simulation endpoints, plus the query parsing they share with suggest-palette.
+use axum::{Json, extract::Query};
+use axum::http::StatusCode;
+use serde_json::json;
+
+// "all"/"true" for every condition, "false"/empty for none, otherwise a comma-separated list
+pub(crate) fn cvd_conditions(list: Option<&str>) -> Result<Vec<crate::palette::cvd::Condition>, (StatusCode, String)> {
+    use crate::palette::cvd::Condition;
+    match list.map(|s| s.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("" | "false") => Ok(vec![]),
+        Some("all" | "true") => Ok(Condition::ALL.to_vec()),
+        Some(l) => l.split(',').map(|c| Condition::parse(c).ok_or_else(|| {
+            let known: Vec<&str> = Condition::ALL.iter().map(|c| c.as_str()).collect();
+            (StatusCode::BAD_REQUEST, format!("unknown CVD condition '{}'; expected all or any of: {}", c.trim(), known.join(", ")))
+        })).collect(),
+    }
+}
+
+pub(crate) fn contrast_model(name: Option<&str>) -> Result<crate::palette::solver::ContrastModel, (StatusCode, String)> {
+    use crate::palette::solver::ContrastModel;
+    match name {
+        None => Ok(ContrastModel::default()),
+        Some(m) => ContrastModel::parse(m).ok_or_else(|| (StatusCode::BAD_REQUEST, format!("unknown contrastModel '{}'; expected one of: {}", m, ContrastModel::NAMES.join(", ")))),
+    }
+}
+
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+pub struct AuditQuery {
+    #[serde(rename = "contrastModel")]
+    pub contrast_model: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+pub struct CvdQuery { pub conditions: Option<String> }
+
+/// Each role as seen with each condition, plus the role pairs that collide.
+pub async fn simulate_cvd(Query(q): Query<CvdQuery>, Json(body): Json<serde_json::Value>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    use crate::palette::{color, cvd};
+    let conditions = match q.conditions.as_deref() { None => cvd::Condition::ALL.to_vec(), Some(c) => cvd_conditions(Some(c))? };
+    let (palette, invalid) = palette_body(&body)?;
+    let simulated: serde_json::Map<String, serde_json::Value> = conditions.iter().map(|&c| {
+        let roles: serde_json::Map<String, serde_json::Value> = palette.iter().map(|(k, v)| (k.clone(), json!(color::to_hex(cvd::simulate(*v, c))))).collect();
+        (c.as_str().to_string(), serde_json::Value::Object(roles))
+    }).collect();
+    let colors: Vec<(String, crate::palette::color::Rgb)> = palette.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    let names: Vec<&str> = palette.keys().map(|k| k.as_str()).collect();
+    let pairs = cvd::pairs(&names);
+    Ok(Json(json!({
+        "conditions": conditions,
+        "threshold": (cvd::COLLISION_DELTA_E as f64 * 1000.0).round() / 1000.0,
+        "pairs": pairs,
+        "collisions": cvd::collisions(&colors, &pairs, &conditions),
+        "simulated": simulated,
+        "invalid": invalid,
+    })))
+}
+
+// `{palette: {...}}` (a guide, user inputs) or a bare role → color object
+fn palette_body(body: &serde_json::Value) -> Result<crate::palette::audit::Parsed, (StatusCode, String)> {
+    let raw = body.get("palette").and_then(|p| p.as_object()).or_else(|| body.as_object())
+        .ok_or((StatusCode::BAD_REQUEST, "expected a palette object".to_string()))?;
+    if raw.len() > crate::palette::audit::MAX_ROLES {
+        return Err((StatusCode::PAYLOAD_TOO_LARGE, format!("palette has {} roles; at most {} are accepted", raw.len(), crate::palette::audit::MAX_ROLES)));
+    }
+    let (palette, invalid) = crate::palette::audit::parse_palette(raw);
+    if palette.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "palette has no hex or oklch() colors".to_string()));
+    }
+    Ok((palette, invalid))
+}
+
+/// Audits `{palette: {...}}` (a guide, user inputs) or a bare role → color object.
+pub async fn audit_palette(Query(q): Query<AuditQuery>, Json(body): Json<serde_json::Value>) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let model = contrast_model(q.contrast_model.as_deref())?;
+    let (palette, invalid) = palette_body(&body)?;
+    let mut report = crate::palette::audit::audit(&palette, model);
+    report["invalid"] = json!(invalid);
+    Ok(Json(report))
+}
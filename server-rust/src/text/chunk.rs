@@ -0,0 +1,112 @@
+// Splits long documents on structural boundaries (sections, then paragraphs, then sentences) so each
+// piece fits comfortably in one model call. Concatenating body + sep of every chunk yields the input.
+
+use super::tokenize;
+
+#[derive(Debug, Clone)]
+pub struct Chunk { pub body: String, pub sep: String }
+
+struct Block { body: String, sep: String, words: usize, section: bool }
+
+pub fn split(text: &str, target_words: usize) -> Vec<Chunk> {
+    let mut chunks: Vec<Chunk> = Vec::new();
+    let mut cur = String::new();
+    let mut cur_sep = String::new();
+    let mut cur_words = 0usize;
+    for b in blocks(text, target_words) {
+        // New section heading: start fresh once the current chunk has some substance
+        let boundary = cur_words > 0 && (cur_words + b.words > target_words || (b.section && cur_words >= target_words / 2));
+        if boundary {
+            chunks.push(Chunk { body: std::mem::take(&mut cur), sep: std::mem::take(&mut cur_sep) });
+            cur_words = 0;
+        }
+        cur.push_str(&cur_sep);
+        cur.push_str(&b.body);
+        cur_sep = b.sep;
+        cur_words += b.words;
+    }
+    if !cur.is_empty() || !cur_sep.is_empty() { chunks.push(Chunk { body: cur, sep: cur_sep }); }
+    chunks
+}
+
+// Paragraph-level blocks (blank-line separated, fenced code kept whole); oversized paragraphs split by sentence
+fn blocks(text: &str, target_words: usize) -> Vec<Block> {
+    let mut out: Vec<Block> = Vec::new();
+    let mut body = String::new();
+    let mut sep = String::new();
+    let mut in_fence = false;
+    for line in text.split_inclusive('\n') {
+        let t = line.trim_start();
+        let blank = line.trim().is_empty();
+        if !in_fence && blank && !body.is_empty() { sep.push_str(line); continue; }
+        if !sep.is_empty() {
+            push_block(&mut out, std::mem::take(&mut body), std::mem::take(&mut sep), target_words);
+        }
+        if t.starts_with("```") || t.starts_with("~~~") { in_fence = !in_fence; }
+        body.push_str(line);
+    }
+    // Move trailing newlines of the final block into its separator
+    let trimmed = body.trim_end_matches(['\n', '\r']).len();
+    let tail = body.split_off(trimmed);
+    sep.insert_str(0, &tail);
+    if !body.is_empty() || !sep.is_empty() { push_block(&mut out, body, sep, target_words); }
+    out
+}
+
+fn push_block(out: &mut Vec<Block>, body: String, sep: String, target_words: usize) {
+    let (body, sep) = { let t = body.trim_end_matches(['\n', '\r']).len(); let mut b = body; let tail = b.split_off(t); (b, format!("{}{}", tail, sep)) };
+    let words = tokenize::words(&body).len();
+    let section = body.trim_start().starts_with('#');
+    let fenced = body.trim_start().starts_with("```") || body.trim_start().starts_with("~~~");
+    if words <= target_words * 2 || fenced {
+        out.push(Block { body, sep, words, section });
+        return;
+    }
+    // Oversized paragraph: break at sentence ends
+    let sentences = tokenize::sentences(&body);
+    let mut start = 0;
+    let mut acc = 0;
+    let mut pieces: Vec<(usize, usize)> = Vec::new();
+    for s in &sentences {
+        acc += tokenize::words(s.text).len();
+        if acc >= target_words { pieces.push((start, s.end)); start = s.end; acc = 0; }
+    }
+    if start < body.len() { pieces.push((start, body.len())); }
+    let last = pieces.len().saturating_sub(1);
+    for (i, (s, e)) in pieces.into_iter().enumerate() {
+        let piece = &body[s..e];
+        // Leading whitespace becomes the previous block's separator; with nothing before it, it stays in the body
+        let lead = match out.last_mut() {
+            Some(prev) => { let n = piece.len() - piece.trim_start().len(); prev.sep.push_str(&piece[..n]); n }
+            None => 0,
+        };
+        let piece = &piece[lead..];
+        let piece_sep = if i == last { sep.clone() } else { String::new() };
+        out.push(Block { body: piece.to_string(), sep: piece_sep, words: tokenize::words(piece).len(), section: section && i == 0 });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_concatenate_back_to_input() {
+        let para = "One short sentence here. ".repeat(30);
+        let doc = format!("# Intro\n\n{}\n\n## Part two\n\n{}\n\n```\ncode stays whole\n```\n", para.trim(), para.trim());
+        let chunks = split(&doc, 60);
+        assert!(chunks.len() > 2);
+        let joined: String = chunks.iter().map(|c| format!("{}{}", c.body, c.sep)).collect();
+        assert_eq!(joined, doc);
+    }
+
+    #[test]
+    fn leading_indent_of_an_oversized_first_paragraph_is_kept() {
+        let doc = format!("   {}\n", "One short sentence here. ".repeat(30).trim());
+        let chunks = split(&doc, 20);
+        assert!(chunks.len() > 1);
+        assert!(chunks[0].body.starts_with("   One"));
+        let joined: String = chunks.iter().map(|c| format!("{}{}", c.body, c.sep)).collect();
+        assert_eq!(joined, doc);
+    }
+}
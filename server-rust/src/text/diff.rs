@@ -0,0 +1,186 @@
+use serde::Serialize;
+
+// Above this many LCS cells we diff line-by-line first and only word-diff the changed blocks
+const MAX_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Op { Keep, Insert, Delete }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffSpan { pub op: Op, pub text: String }
+
+/// Word-level diff of `a` → `b`. Whitespace and punctuation are their own tokens, so spans concatenate
+/// back to the original (keep + delete) and the result (keep + insert).
+pub fn word_diff(a: &str, b: &str) -> Vec<DiffSpan> {
+    let mut out = Vec::new();
+    diff_into(&tokens(a), &tokens(b), &mut out, true);
+    out
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeStats {
+    pub keptWords: usize,
+    pub insertedWords: usize,
+    pub deletedWords: usize,
+    // 1 − Dice similarity over words: 0 = identical, 1 = nothing kept
+    pub changeRatio: f32,
+    // rewritten words / original words; null when the original had none
+    pub lengthRatio: Option<f32>,
+}
+
+pub fn change_stats(spans: &[DiffSpan]) -> ChangeStats {
+    let (mut kept, mut ins, mut del) = (0usize, 0usize, 0usize);
+    for s in spans {
+        let n = super::tokenize::words(&s.text).len();
+        match s.op { Op::Keep => kept += n, Op::Insert => ins += n, Op::Delete => del += n }
+    }
+    let total = 2 * kept + ins + del;
+    let change = if total == 0 { 0.0 } else { (ins + del) as f32 / total as f32 };
+    let orig = kept + del;
+    let length = if orig == 0 { (ins == 0).then_some(1.0) } else { Some((kept + ins) as f32 / orig as f32) };
+    ChangeStats {
+        keptWords: kept,
+        insertedWords: ins,
+        deletedWords: del,
+        changeRatio: (change * 1000.0).round() / 1000.0,
+        lengthRatio: length.map(|l| (l * 1000.0).round() / 1000.0),
+    }
+}
+
+/// `<ins>`/`<del>` markup for the spans; all text is HTML-escaped.
+pub fn to_html(spans: &[DiffSpan]) -> String {
+    let mut out = String::new();
+    for s in spans {
+        let t = escape_html(&s.text);
+        match s.op {
+            Op::Keep => out.push_str(&t),
+            Op::Insert => { out.push_str("<ins>"); out.push_str(&t); out.push_str("</ins>"); }
+            Op::Delete => { out.push_str("<del>"); out.push_str(&t); out.push_str("</del>"); }
+        }
+    }
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn diff_into(a: &[&str], b: &[&str], out: &mut Vec<DiffSpan>, allow_lines: bool) {
+    // Trim common prefix/suffix before the quadratic part
+    let pre = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suf = a[pre..].iter().rev().zip(b[pre..].iter().rev()).take_while(|(x, y)| x == y).count();
+    for t in &a[..pre] { push(out, Op::Keep, t); }
+    let (am, bm) = (&a[pre..a.len() - suf], &b[pre..b.len() - suf]);
+
+    if am.len().saturating_mul(bm.len()) <= MAX_CELLS {
+        for (op, t) in lcs(am, bm) { push(out, op, t); }
+    } else if allow_lines {
+        let (ja, jb) = (am.concat(), bm.concat());
+        let (la, lb): (Vec<&str>, Vec<&str>) = (ja.split_inclusive('\n').collect(), jb.split_inclusive('\n').collect());
+        if la.len().saturating_mul(lb.len()) <= MAX_CELLS {
+            // Word-diff each run of changed lines, keep unchanged lines verbatim
+            let (mut del, mut ins) = (String::new(), String::new());
+            for (op, line) in lcs(&la, &lb) {
+                match op {
+                    Op::Delete => del.push_str(line),
+                    Op::Insert => ins.push_str(line),
+                    Op::Keep => { flush_block(&mut del, &mut ins, out); push(out, Op::Keep, line); }
+                }
+            }
+            flush_block(&mut del, &mut ins, out);
+        } else {
+            for t in am { push(out, Op::Delete, t); }
+            for t in bm { push(out, Op::Insert, t); }
+        }
+    } else {
+        for t in am { push(out, Op::Delete, t); }
+        for t in bm { push(out, Op::Insert, t); }
+    }
+    for t in &a[a.len() - suf..] { push(out, Op::Keep, t); }
+}
+
+fn flush_block(del: &mut String, ins: &mut String, out: &mut Vec<DiffSpan>) {
+    if del.is_empty() && ins.is_empty() { return; }
+    diff_into(&tokens(del), &tokens(ins), out, false);
+    del.clear();
+    ins.clear();
+}
+
+fn lcs<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let (n, m) = (a.len(), b.len());
+    // dp[i][j] = LCS length of a[i..], b[j..]
+    let mut dp = vec![0u32; (n + 1) * (m + 1)];
+    let idx = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[idx(i, j)] = if a[i] == b[j] { dp[idx(i + 1, j + 1)] + 1 } else { dp[idx(i + 1, j)].max(dp[idx(i, j + 1)]) };
+        }
+    }
+    let mut ops = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] { ops.push((Op::Keep, a[i])); i += 1; j += 1; }
+        else if dp[idx(i + 1, j)] >= dp[idx(i, j + 1)] { ops.push((Op::Delete, a[i])); i += 1; }
+        else { ops.push((Op::Insert, b[j])); j += 1; }
+    }
+    ops.extend(a[i..].iter().map(|t| (Op::Delete, *t)));
+    ops.extend(b[j..].iter().map(|t| (Op::Insert, *t)));
+    ops
+}
+
+fn push(out: &mut Vec<DiffSpan>, op: Op, t: &str) {
+    if t.is_empty() { return; }
+    match out.last_mut() {
+        Some(last) if last.op == op => last.text.push_str(t),
+        _ => out.push(DiffSpan { op, text: t.to_string() }),
+    }
+}
+
+// Words, whitespace runs, and single punctuation characters
+fn tokens(s: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut kind: Option<u8> = None;
+    for (i, c) in s.char_indices() {
+        let k = if c.is_alphanumeric() || c == '\'' || c == '’' { 0 } else if c.is_whitespace() { 1 } else { 2 };
+        if kind != Some(k) || k == 2 {
+            if i > start { out.push(&s[start..i]); }
+            start = i;
+        }
+        kind = Some(k);
+    }
+    if start < s.len() { out.push(&s[start..]); }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_rebuild_both_sides() {
+        let (a, b) = ("We help teams ship faster.", "We help small teams ship, faster!");
+        let d = word_diff(a, b);
+        let left: String = d.iter().filter(|s| s.op != Op::Insert).map(|s| s.text.as_str()).collect();
+        let right: String = d.iter().filter(|s| s.op != Op::Delete).map(|s| s.text.as_str()).collect();
+        assert_eq!(left, a);
+        assert_eq!(right, b);
+        assert!(d.iter().any(|s| s.op == Op::Insert && s.text.contains("small")));
+    }
+
+    #[test]
+    fn stats_and_html_markup() {
+        let d = word_diff("Fast & cheap.", "Fast <b>and</b> cheap.");
+        let s = change_stats(&d);
+        assert_eq!((s.keptWords, s.insertedWords, s.deletedWords), (2, 3, 0));
+        assert_eq!((s.changeRatio, s.lengthRatio), (0.429, Some(2.5)));
+        assert_eq!(to_html(&d), "Fast <del>&amp;</del><ins>&lt;b&gt;and&lt;/b&gt;</ins> cheap.");
+
+        assert_eq!(change_stats(&word_diff("", "")).lengthRatio, Some(1.0));
+        // Nothing to compare against: no ratio rather than a made-up one
+        let s = change_stats(&word_diff("", "Hello there"));
+        assert_eq!((s.insertedWords, s.changeRatio, s.lengthRatio), (2, 1.0, None));
+    }
+}
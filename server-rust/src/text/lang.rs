@@ -0,0 +1,62 @@
+// Stopword-frequency language detection for the languages brand guides commonly declare.
+use serde::Serialize;
+
+const STOPWORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "is", "are", "of", "to", "in", "that", "it", "for", "with", "you", "this", "was", "on", "be", "not", "have", "we", "your"]),
+    ("fr", &["le", "la", "les", "des", "et", "est", "une", "un", "du", "que", "qui", "pour", "dans", "pas", "vous", "nous", "avec", "sur", "ce", "au"]),
+    ("es", &["el", "los", "las", "y", "es", "una", "que", "en", "por", "para", "con", "no", "se", "del", "lo", "su", "como", "pero", "más", "usted"]),
+    ("de", &["der", "die", "das", "und", "ist", "nicht", "ein", "eine", "zu", "mit", "sie", "wir", "auf", "für", "den", "dem", "von", "ich", "auch", "es"]),
+    ("pt", &["o", "os", "as", "e", "é", "um", "uma", "que", "em", "para", "com", "não", "do", "da", "no", "na", "se", "você", "mais", "seu"]),
+    ("it", &["il", "lo", "gli", "e", "è", "un", "una", "che", "di", "per", "con", "non", "del", "della", "sono", "ci", "questo", "anche", "nel", "al"]),
+];
+
+// Fewer stopword hits than this and we don't guess
+const MIN_HITS: usize = 3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Detection { pub language: &'static str, pub confidence: f32 }
+
+pub fn detect(text: &str) -> Option<Detection> {
+    let lower = text.to_lowercase();
+    let words = super::tokenize::words(&lower);
+    let mut scores: Vec<(&'static str, usize)> = STOPWORDS.iter()
+        .map(|(code, list)| (*code, words.iter().filter(|w| list.contains(&w.text)).count()))
+        .collect();
+    scores.sort_by_key(|s| std::cmp::Reverse(s.1));
+    let (best, hits) = scores[0];
+    if hits < MIN_HITS { return None; }
+    let runner_up = scores[1].1;
+    // Share of the top two scores: 0.5 = coin flip, 1.0 = unambiguous
+    let confidence = hits as f32 / (hits + runner_up) as f32;
+    Some(Detection { language: best, confidence: (confidence * 100.0).round() / 100.0 })
+}
+
+/// Primary subtag of a BCP 47 tag, lowercased: "fr-CA" → "fr".
+pub fn primary(tag: &str) -> String {
+    tag.trim().split(['-', '_']).next().unwrap_or("").to_ascii_lowercase()
+}
+
+pub fn name(code: &str) -> &str {
+    match primary(code).as_str() {
+        "en" => "English",
+        "fr" => "French",
+        "es" => "Spanish",
+        "de" => "German",
+        "pt" => "Portuguese",
+        "it" => "Italian",
+        _ => code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_english_and_french() {
+        assert_eq!(detect("We help you plan the move and we stay with you for the first year.").unwrap().language, "en");
+        assert_eq!(detect("Nous vous aidons à planifier le déménagement et nous restons avec vous pour la première année.").unwrap().language, "fr");
+        assert!(detect("Hello there").is_none());
+        assert_eq!(primary("fr_CA"), "fr");
+    }
+}
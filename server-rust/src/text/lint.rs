@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::tokenize::{self, Span};
+use crate::models::{BrandGuide, LintOptions};
+
+/// Sentence length (words) past which lint and readability call a sentence long.
+pub const DEFAULT_MAX_SENTENCE_WORDS: usize = 25;
+
+const BE_FORMS: &[&str] = &["am", "is", "are", "was", "were", "be", "been", "being", "isn't", "aren't", "wasn't", "weren't"];
+const IRREGULAR_PARTICIPLES: &[&str] = &[
+    "done", "made", "given", "taken", "written", "seen", "known", "shown", "built", "sent", "held", "kept",
+    "found", "told", "paid", "left", "brought", "bought", "caught", "taught", "thought", "chosen", "driven",
+    "broken", "spoken", "won", "run", "put", "set", "cut", "read", "led", "met", "sold", "understood", "hidden", "forgotten",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub severity: &'static str,
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+    pub excerpt: String,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LintReport {
+    pub findings: Vec<Finding>,
+    pub counts: BTreeMap<&'static str, usize>,
+}
+
+// A term to match on stemmed word sequences, with the rule it reports under
+struct TermRule { stems: Vec<String>, rule: &'static str, message: String, suggestion: Option<String> }
+
+// The stemmer is English; other languages match whole words, case-insensitively
+fn normalize(word: &str, english: bool) -> String {
+    if english { tokenize::stem(word) } else { word.to_lowercase() }
+}
+
+impl TermRule {
+    fn new(term: &str, english: bool, rule: &'static str, message: String, suggestion: Option<String>) -> Option<Self> {
+        let stems: Vec<String> = tokenize::words(term).iter().map(|w| normalize(w.text, english)).collect();
+        if stems.is_empty() { return None; }
+        Some(Self { stems, rule, message, suggestion })
+    }
+}
+
+pub fn lint(text: &str, guide: Option<&BrandGuide>, opts: Option<&LintOptions>) -> LintReport {
+    let donts: Vec<String> = guide.map(|g| g.tone.dosAndDonts.donts.iter().map(|d| d.to_lowercase()).collect()).unwrap_or_default();
+    let guide_bans = |kw: &str| donts.iter().any(|d| d.contains(kw));
+
+    // Explicit language wins; otherwise detect it, assuming English when unsure
+    let lang = opts.and_then(|o| o.language.clone()).filter(|l| !l.trim().is_empty())
+        .or_else(|| super::lang::detect(text).map(|d| d.language.to_string()))
+        .unwrap_or_else(|| "en".to_string());
+    let english = super::lang::primary(&lang) == "en";
+
+    let mut terms: Vec<TermRule> = Vec::new();
+    let extra = guide.map(|g| g.bannedTerms.as_slice()).unwrap_or_default();
+    for t in crate::prompts::banned_terms(&lang, extra) {
+        if let Some(r) = TermRule::new(&t, english, "banned-term", format!("\"{}\" is on the banned term list", t), None) { terms.push(r); }
+    }
+    for p in guide.map(|g| g.preferredTerms.as_slice()).unwrap_or_default() {
+        let msg = format!("Guide prefers \"{}\" over \"{}\"", p.prefer, p.avoid);
+        if let Some(r) = TermRule::new(&p.avoid, english, "preferred-term", msg, Some(p.prefer.clone())) { terms.push(r); }
+    }
+    for dont in guide.map(|g| g.tone.dosAndDonts.donts.as_slice()).unwrap_or_default() {
+        for phrase in quoted_phrases(dont) {
+            if let Some(r) = TermRule::new(&phrase, english, "guide-dont", format!("Guide don't: {}", dont), None) { terms.push(r); }
+        }
+    }
+
+    let mut findings = Vec::new();
+    for (rule, s, e) in match_terms(text, &terms, english) {
+        // Skip duplicates when a phrase is both globally banned and quoted in a don't
+        if findings.iter().any(|f: &Finding| f.rule == rule.rule && f.start == tokenize::char_offset(text, s) && f.end == tokenize::char_offset(text, e)) { continue; }
+        findings.push(finding(text, rule.rule, "error", rule.message.clone(), s, e, rule.suggestion.clone()));
+    }
+
+    if opts.and_then(|o| o.exclamations).unwrap_or(true) {
+        let sev = if guide_bans("exclamation") { "error" } else { "warning" };
+        for (i, _) in text.match_indices('!') {
+            if text[i + 1..].starts_with('[') || text[i + 1..].starts_with('=') { continue; } // markdown images, "!="
+            findings.push(finding(text, "exclamation", sev, "Exclamation mark".to_string(), i, i + 1, Some("End with a period".into())));
+        }
+    }
+
+    let max_words = opts.and_then(|o| o.maxSentenceWords).unwrap_or(DEFAULT_MAX_SENTENCE_WORDS);
+    // The passive heuristic is English-only; default it off for other languages
+    let passive_on = opts.and_then(|o| o.passiveVoice).unwrap_or(english);
+    let passive_sev = if guide_bans("passive") { "error" } else { "warning" };
+    for sentence in tokenize::sentences(text) {
+        let sw = tokenize::words(sentence.text);
+        if max_words > 0 && sw.len() > max_words {
+            let msg = format!("Sentence has {} words (limit {})", sw.len(), max_words);
+            findings.push(finding(text, "sentence-length", "warning", msg, sentence.start, sentence.end, Some("Split into shorter sentences".into())));
+        }
+        if passive_on {
+            for (s, e) in passive_spans(&sw) {
+                findings.push(finding(text, "passive-voice", passive_sev, "Possible passive voice".to_string(), sentence.start + s, sentence.start + e, Some("Name who does the action".into())));
+            }
+        }
+    }
+
+    findings.sort_by_key(|f| (f.start, f.end));
+    let mut counts = BTreeMap::new();
+    for f in &findings { *counts.entry(f.rule).or_insert(0) += 1; }
+    LintReport { findings, counts }
+}
+
+/// Banned terms (built-in list for `lang` plus `extra`) present in `text`, phrase-aware and stemmed for English,
+/// as the excerpts that matched.
+pub fn find_banned(text: &str, lang: &str, extra: &[String]) -> Vec<String> {
+    let english = super::lang::primary(lang) == "en";
+    let terms: Vec<TermRule> = crate::prompts::banned_terms(lang, extra).iter().filter_map(|t| TermRule::new(t, english, "banned-term", String::new(), None)).collect();
+    let mut out: Vec<String> = Vec::new();
+    for (_, s, e) in match_terms(text, &terms, english) {
+        let hit = text[s..e].to_string();
+        if !out.contains(&hit) { out.push(hit); }
+    }
+    out
+}
+
+// Byte spans where a rule's stem sequence occurs in the text
+fn match_terms<'r>(text: &str, terms: &'r [TermRule], english: bool) -> Vec<(&'r TermRule, usize, usize)> {
+    let words = tokenize::words(text);
+    let stems: Vec<String> = words.iter().map(|w| normalize(w.text, english)).collect();
+    let mut out = Vec::new();
+    for rule in terms {
+        let n = rule.stems.len();
+        if n > stems.len() { continue; }
+        for i in 0..=stems.len() - n {
+            if stems[i..i + n] == rule.stems[..] { out.push((rule, words[i].start, words[i + n - 1].end)); }
+        }
+    }
+    out
+}
+
+fn finding(text: &str, rule: &'static str, severity: &'static str, message: String, s: usize, e: usize, suggestion: Option<String>) -> Finding {
+    Finding {
+        rule,
+        severity,
+        message,
+        start: tokenize::char_offset(text, s),
+        end: tokenize::char_offset(text, e),
+        excerpt: text[s..e].to_string(),
+        suggestion,
+    }
+}
+
+/// True when the sentence contains a "be + past participle" construction.
+pub fn has_passive(sentence: &str) -> bool { !passive_spans(&tokenize::words(sentence)).is_empty() }
+
+// Spans (relative to the sentence) of "be" + optional adverb/not + past participle
+fn passive_spans(words: &[Span<'_>]) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    for (i, w) in words.iter().enumerate() {
+        if !BE_FORMS.contains(&w.text.to_lowercase().as_str()) { continue; }
+        let mut j = i + 1;
+        while j < words.len() && j <= i + 2 {
+            let lw = words[j].text.to_lowercase();
+            if lw == "not" || (lw.ends_with("ly") && lw.len() > 4) { j += 1; continue; }
+            break;
+        }
+        let Some(p) = words.get(j) else { continue };
+        let lp = p.text.to_lowercase();
+        let participle = (lp.ends_with("ed") && lp.len() > 3) || IRREGULAR_PARTICIPLES.contains(&lp.as_str());
+        if participle { out.push((w.start, p.end)); }
+    }
+    out
+}
+
+// Phrases quoted in a guide don't, e.g. `Say "world-leading"` or 'circle back' (apostrophes are not quotes)
+fn quoted_phrases(s: &str) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let closing = |c: char| match c { '"' => Some('"'), '“' => Some('”'), '‘' => Some('’'), '\'' => Some('\''), _ => None };
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let Some(close) = closing(chars[i]) else { i += 1; continue };
+        if i > 0 && chars[i - 1].is_alphanumeric() { i += 1; continue; }
+        let end = (i + 1..chars.len()).find(|&j| chars[j] == close && !chars.get(j + 1).map(|c| c.is_alphanumeric()).unwrap_or(false));
+        let Some(end) = end else { i += 1; continue };
+        let phrase: String = chars[i + 1..end].iter().collect();
+        let phrase = phrase.trim();
+        if !phrase.is_empty() && phrase.split_whitespace().count() <= 5 { out.push(phrase.to_string()); }
+        i = end + 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_banned_terms_with_stemming_and_phrases() {
+        let r = lint("We optimized onboarding. It's a paradigm shift for synergies.", None, None);
+        let hits: Vec<&str> = r.findings.iter().filter(|f| f.rule == "banned-term").map(|f| f.excerpt.as_str()).collect();
+        assert_eq!(hits, vec!["optimized", "paradigm shift", "synergies"]);
+    }
+
+    #[test]
+    fn flags_exclamations_long_sentences_and_passive() {
+        let opts = LintOptions { maxSentenceWords: Some(5), exclamations: None, passiveVoice: None, language: None };
+        let r = lint("The report was written by our team over many weeks. Great!", None, Some(&opts));
+        assert_eq!(r.counts.get("exclamation"), Some(&1));
+        assert_eq!(r.counts.get("sentence-length"), Some(&1));
+        assert!(r.findings.iter().any(|f| f.rule == "passive-voice" && f.excerpt == "was written"));
+    }
+
+    #[test]
+    fn french_text_uses_the_french_list_without_english_rules() {
+        let text = "Notre solution est robuste et dynamique. La solution clé en main a été testée par nous.";
+        let opts = LintOptions { maxSentenceWords: None, exclamations: None, passiveVoice: None, language: Some("fr-CA".into()) };
+        let r = lint(text, None, Some(&opts));
+        let hits: Vec<&str> = r.findings.iter().filter(|f| f.rule == "banned-term").map(|f| f.excerpt.as_str()).collect();
+        assert_eq!(hits, vec!["robuste", "solution clé en main"]);
+        assert!(!r.counts.contains_key("passive-voice"));
+        // Detected without an explicit language, too
+        assert_eq!(lint(text, None, None).counts.get("banned-term"), Some(&2));
+    }
+}
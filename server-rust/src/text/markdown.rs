@@ -0,0 +1,84 @@
+// Line-based Markdown outline: enough to tell whether a rewrite kept the document's skeleton.
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Structure {
+    pub heading_levels: Vec<usize>,
+    pub list_items: usize,
+    pub links: Vec<String>,
+    pub code_blocks: Vec<String>,
+}
+
+pub fn structure(text: &str) -> Structure {
+    let mut s = Structure::default();
+    let mut fence: Option<(String, String)> = None; // (marker, content)
+    for line in text.lines() {
+        let t = line.trim_start();
+        if let Some((marker, body)) = fence.as_mut() {
+            if t.starts_with(marker.as_str()) {
+                s.code_blocks.push(std::mem::take(body));
+                fence = None;
+            } else {
+                body.push_str(line);
+                body.push('\n');
+            }
+            continue;
+        }
+        if t.starts_with("```") || t.starts_with("~~~") {
+            fence = Some((t[..3].to_string(), String::new()));
+            continue;
+        }
+        let hashes = t.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&hashes) && t[hashes..].starts_with(' ') { s.heading_levels.push(hashes); }
+        if is_list_item(t) { s.list_items += 1; }
+        s.links.extend(link_targets(t));
+    }
+    // Unterminated fence still counts as a block
+    if let Some((_, body)) = fence { s.code_blocks.push(body); }
+    s.links.sort();
+    s
+}
+
+fn is_list_item(t: &str) -> bool {
+    if t.starts_with("- ") || t.starts_with("* ") || t.starts_with("+ ") { return true; }
+    let digits = t.chars().take_while(|c| c.is_ascii_digit()).count();
+    digits > 0 && (t[digits..].starts_with(". ") || t[digits..].starts_with(") "))
+}
+
+// `[text](url)` targets, `<https://…>` autolinks, and bare http(s) URLs
+pub fn link_targets(line: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = line;
+    while let Some(i) = rest.find("](") {
+        let after = &rest[i + 2..];
+        let Some(j) = after.find(')') else { break };
+        let target = after[..j].split_whitespace().next().unwrap_or("").to_string();
+        if !target.is_empty() { out.push(target); }
+        rest = &after[j + 1..];
+    }
+    // Bare/autolinked URLs not already captured as link targets
+    for scheme in ["https://", "http://"] {
+        let mut rest = line;
+        while let Some(i) = rest.find(scheme) {
+            let preceded_by_paren = rest[..i].ends_with("](");
+            let url: String = rest[i..].chars().take_while(|c| !c.is_whitespace() && !matches!(c, ')' | '>' | '"' | '\'')).collect();
+            let url = url.trim_end_matches(['.', ',', ';', ':', '!', '?']).to_string();
+            if !preceded_by_paren && !url.is_empty() { out.push(url.clone()); }
+            rest = &rest[i + url.len().max(scheme.len())..];
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outlines_headings_lists_links_and_code() {
+        let s = structure("# Title\n#hashtag\n\n1. One\n2) Two\n* Three\n-not a list\n\nRead [docs](https://a.io/docs \"Docs\") and <https://b.io>.\n\n```sh\n# not a heading\n- not an item\n```\n## End\n");
+        assert_eq!(s.heading_levels, vec![1, 2]);
+        assert_eq!(s.list_items, 3);
+        assert_eq!(s.links, vec!["https://a.io/docs", "https://b.io"]);
+        assert_eq!(s.code_blocks, vec!["# not a heading\n- not an item\n"]);
+    }
+}
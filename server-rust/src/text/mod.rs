@@ -0,0 +1,10 @@
+pub mod tokenize;
+pub mod lint;
+pub mod readability;
+pub mod diff;
+pub mod markdown;
+pub mod protect;
+pub mod chunk;
+pub mod lang;
+pub mod stylometry;
+pub mod similarity;
@@ -0,0 +1,148 @@
+// Swap spans the model must not touch (code, URLs, template variables, HTML tags) for opaque
+// placeholders before rewriting, then put them back and report any that did not survive.
+
+pub const PLACEHOLDER_RULE: &str = "\n\nThe text contains placeholders like @@P1@@ standing in for code, links, HTML tags or template variables. Copy every placeholder exactly as written, once, in the matching spot. Never translate, split, or drop them.";
+
+#[derive(Debug, Clone, Default)]
+pub struct Protected {
+    pub text: String,
+    // (placeholder, original span) in document order
+    pub spans: Vec<(String, String)>,
+}
+
+pub fn protect(input: &str) -> Protected {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    ranges.extend(fenced_blocks(input));
+    ranges.extend(delimited(input, "`", "`", false));
+    ranges.extend(delimited(input, "{{", "}}", true));
+    ranges.extend(delimited(input, "{%", "%}", true));
+    ranges.extend(delimited(input, "${", "}", true));
+    ranges.extend(html_tags(input));
+    ranges.extend(link_targets(input));
+    ranges.extend(urls(input));
+
+    // Earliest start wins; drop anything overlapping an accepted range
+    ranges.sort_by_key(|&(s, e)| (s, std::cmp::Reverse(e)));
+    let mut accepted: Vec<(usize, usize)> = Vec::new();
+    for (s, e) in ranges {
+        if s >= e { continue; }
+        if accepted.last().map(|&(_, le)| s < le).unwrap_or(false) { continue; }
+        accepted.push((s, e));
+    }
+
+    let mut out = Protected::default();
+    let mut cursor = 0;
+    for (i, (s, e)) in accepted.into_iter().enumerate() {
+        let token = format!("@@P{}@@", i + 1);
+        out.text.push_str(&input[cursor..s]);
+        out.text.push_str(&token);
+        out.spans.push((token, input[s..e].to_string()));
+        cursor = e;
+    }
+    out.text.push_str(&input[cursor..]);
+    out
+}
+
+/// Puts protected spans back; returns the restored text and the placeholders the model dropped.
+pub fn restore(text: &str, spans: &[(String, String)]) -> (String, Vec<String>) {
+    let mut out = text.to_string();
+    let mut missing = Vec::new();
+    for (token, original) in spans {
+        if out.contains(token.as_str()) { out = out.replace(token.as_str(), original); } else { missing.push(original.clone()); }
+    }
+    (out, missing)
+}
+
+fn fenced_blocks(s: &str) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    let mut open: Option<(usize, &str)> = None;
+    let mut pos = 0;
+    for line in s.split_inclusive('\n') {
+        let t = line.trim_start();
+        let marker = if t.starts_with("```") { Some("```") } else if t.starts_with("~~~") { Some("~~~") } else { None };
+        match (open, marker) {
+            (None, Some(m)) => open = Some((pos, m)),
+            (Some((start, m)), Some(m2)) if m == m2 => { out.push((start, pos + line.trim_end_matches('\n').len())); open = None; }
+            _ => {}
+        }
+        pos += line.len();
+    }
+    if let Some((start, _)) = open { out.push((start, s.len())); }
+    out
+}
+
+// `open … close` ranges; single-line unless `multiline`
+fn delimited(s: &str, open: &str, close: &str, multiline: bool) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(i) = s[from..].find(open) {
+        let start = from + i;
+        let body_start = start + open.len();
+        let Some(j) = s[body_start..].find(close) else { break };
+        let end = body_start + j + close.len();
+        if j > 0 && (multiline || !s[body_start..body_start + j].contains('\n')) { out.push((start, end)); from = end; } else { from = body_start; }
+    }
+    out
+}
+
+fn html_tags(s: &str) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(i) = s[from..].find('<') {
+        let start = from + i;
+        let next = s[start + 1..].chars().next();
+        let is_tag = matches!(next, Some(c) if c.is_ascii_alphabetic() || c == '/' || c == '!');
+        match (is_tag, s[start..].find('>')) {
+            (true, Some(j)) => { out.push((start, start + j + 1)); from = start + j + 1; }
+            _ => from = start + 1,
+        }
+    }
+    out
+}
+
+// The `(url "title")` part of Markdown links and images
+fn link_targets(s: &str) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(i) = s[from..].find("](") {
+        let start = from + i + 2;
+        match s[start..].find(')') {
+            Some(j) if !s[start..start + j].contains('\n') => { out.push((start, start + j)); from = start + j; }
+            _ => from = start,
+        }
+    }
+    out
+}
+
+fn urls(s: &str) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    for scheme in ["https://", "http://", "mailto:"] {
+        let mut from = 0;
+        while let Some(i) = s[from..].find(scheme) {
+            let start = from + i;
+            let len: usize = s[start..].chars().take_while(|c| !c.is_whitespace() && !matches!(c, ')' | '>' | '"' | '\'' | '<')).map(|c| c.len_utf8()).sum();
+            let url = s[start..start + len].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+            out.push((start, start + url.len()));
+            from = start + len.max(scheme.len());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_reports_dropped_spans() {
+        let src = "Hi {{first_name}}, see [docs](https://x.io/a?b=1) or run `make build`.\n<a href=\"/x\">Click</a>";
+        let p = protect(src);
+        assert_eq!(p.spans.len(), 5);
+        assert!(!p.text.contains("https://"));
+        let (back, missing) = restore(&p.text, &p.spans);
+        assert_eq!(back, src);
+        assert!(missing.is_empty());
+        let (_, missing) = restore(&p.text.replace("@@P1@@", "there"), &p.spans);
+        assert_eq!(missing, vec!["{{first_name}}".to_string()]);
+    }
+}
@@ -0,0 +1,148 @@
+use serde::Serialize;
+
+use super::lint::DEFAULT_MAX_SENTENCE_WORDS;
+use super::tokenize;
+use crate::models::ReadabilityTargets;
+
+// Defaults when the guide sets no targets: plain-language copy for a general audience
+const DEFAULT_GRADE: (f32, f32) = (6.0, 10.0);
+const DEFAULT_MAX_AVG_SENTENCE: f32 = 20.0;
+const DEFAULT_MAX_LONG_SHARE: f32 = 0.2;
+const DEFAULT_MAX_PASSIVE: f32 = 0.15;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct Metrics {
+    pub words: usize,
+    pub sentences: usize,
+    pub fleschKincaidGrade: f32,
+    pub avgSentenceLength: f32,
+    pub longSentenceShare: f32,
+    pub passiveRatio: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Deviation {
+    pub metric: &'static str,
+    pub value: f32,
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+    pub message: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadabilityReport {
+    pub metrics: Metrics,
+    pub targets: ReadabilityTargets,
+    pub targetsSource: &'static str,
+    pub deviations: Vec<Deviation>,
+}
+
+pub fn measure(text: &str, long_sentence_words: usize) -> Metrics {
+    let sentences = tokenize::sentences(text);
+    let mut words = 0usize;
+    let mut syllables = 0usize;
+    let mut long = 0usize;
+    let mut passive = 0usize;
+    for s in &sentences {
+        let w = tokenize::words(s.text);
+        words += w.len();
+        syllables += w.iter().map(|t| count_syllables(t.text)).sum::<usize>();
+        if w.len() > long_sentence_words { long += 1; }
+        if super::lint::has_passive(s.text) { passive += 1; }
+    }
+    let n = sentences.len().max(1) as f32;
+    let wc = words.max(1) as f32;
+    let grade = if words == 0 { 0.0 } else { 0.39 * (wc / n) + 11.8 * (syllables as f32 / wc) - 15.59 };
+    Metrics {
+        words,
+        sentences: sentences.len(),
+        fleschKincaidGrade: round1(grade.max(0.0)),
+        avgSentenceLength: round1(words as f32 / n),
+        longSentenceShare: round2(long as f32 / n),
+        passiveRatio: round2(passive as f32 / n),
+    }
+}
+
+pub fn report(text: &str, targets: Option<&ReadabilityTargets>) -> ReadabilityReport {
+    let source = if targets.is_some() { "guide" } else { "default" };
+    let t = targets.cloned().unwrap_or_default();
+    let resolved = ReadabilityTargets {
+        gradeMin: t.gradeMin.or(Some(DEFAULT_GRADE.0)),
+        gradeMax: t.gradeMax.or(Some(DEFAULT_GRADE.1)),
+        maxAvgSentenceLength: t.maxAvgSentenceLength.or(Some(DEFAULT_MAX_AVG_SENTENCE)),
+        longSentenceWords: t.longSentenceWords.or(Some(DEFAULT_MAX_SENTENCE_WORDS)),
+        maxLongSentenceShare: t.maxLongSentenceShare.or(Some(DEFAULT_MAX_LONG_SHARE)),
+        maxPassiveRatio: t.maxPassiveRatio.or(Some(DEFAULT_MAX_PASSIVE)),
+    };
+    let metrics = measure(text, resolved.longSentenceWords.unwrap_or(DEFAULT_MAX_SENTENCE_WORDS));
+
+    let mut deviations = Vec::new();
+    if metrics.words > 0 {
+        let g = metrics.fleschKincaidGrade;
+        let (lo, hi) = (resolved.gradeMin.unwrap_or(0.0), resolved.gradeMax.unwrap_or(f32::MAX));
+        if g < lo || g > hi {
+            let dir = if g > hi { "above" } else { "below" };
+            deviations.push(Deviation { metric: "fleschKincaidGrade", value: g, min: Some(lo), max: Some(hi), message: format!("Grade {:.1} is {} the target {:.0}–{:.0}", g, dir, lo, hi) });
+        }
+        let mut over = |metric: &'static str, value: f32, max: Option<f32>, label: &str| {
+            if let Some(m) = max { if value > m { deviations.push(Deviation { metric, value, min: None, max: Some(m), message: format!("{} {} exceeds the target of {}", label, value, m) }); } }
+        };
+        over("avgSentenceLength", metrics.avgSentenceLength, resolved.maxAvgSentenceLength, "Average sentence length");
+        over("longSentenceShare", metrics.longSentenceShare, resolved.maxLongSentenceShare, "Share of long sentences");
+        over("passiveRatio", metrics.passiveRatio, resolved.maxPassiveRatio, "Passive-voice ratio");
+    }
+    ReadabilityReport { metrics, targets: resolved, targetsSource: source, deviations }
+}
+
+// Vowel-group count with the usual silent-e and "-le" adjustments; good enough for grade estimates
+fn count_syllables(word: &str) -> usize {
+    let w: Vec<char> = word.to_lowercase().chars().filter(|c| c.is_alphabetic()).collect();
+    if w.is_empty() { return 0; }
+    if w.len() <= 3 { return 1; }
+    let vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut count = 0usize;
+    let mut prev = false;
+    for &c in &w {
+        let v = vowel(c);
+        if v && !prev { count += 1; }
+        prev = v;
+    }
+    let n = w.len();
+    let consonant_le = w[n - 2] == 'l' && !vowel(w[n - 3]); // "table", "simple" keep their final syllable
+    if w[n - 1] == 'e' && !consonant_le { count = count.saturating_sub(1); }
+    if w[n - 2] == 'e' && matches!(w[n - 1], 's' | 'd') && !matches!(w[n - 3], 't' | 'd') && !vowel(w[n - 3]) { count = count.saturating_sub(1); }
+    count.max(1)
+}
+
+fn round1(x: f32) -> f32 { (x * 10.0).round() / 10.0 }
+fn round2(x: f32) -> f32 { (x * 100.0).round() / 100.0 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_syllables_for_grade() {
+        assert_eq!(["table", "simple", "make", "jumped", "wanted", "more"].map(count_syllables), [2, 2, 1, 1, 2, 1]);
+        // 7 words, 10 syllables, 2 sentences: 0.39 × 3.5 + 11.8 × 10/7 − 15.59
+        let m = measure("The table is simple. We wanted more.", DEFAULT_MAX_SENTENCE_WORDS);
+        assert_eq!((m.words, m.sentences, m.fleschKincaidGrade, m.avgSentenceLength), (7, 2, 2.6, 3.5));
+        assert_eq!(measure("", DEFAULT_MAX_SENTENCE_WORDS).fleschKincaidGrade, 0.0);
+    }
+
+    #[test]
+    fn long_sentences_follow_the_guide_threshold() {
+        let text = "The table is simple. We wanted more.";
+        assert_eq!(report(text, None).metrics.longSentenceShare, 0.0);
+
+        let targets = ReadabilityTargets { longSentenceWords: Some(3), ..Default::default() };
+        let r = report(text, Some(&targets));
+        assert_eq!((r.targetsSource, r.metrics.longSentenceShare), ("guide", 0.5));
+        let metrics: Vec<&str> = r.deviations.iter().map(|d| d.metric).collect();
+        // Unset targets still fall back to the defaults (grade 6–10, long share 0.2)
+        assert_eq!(metrics, vec!["fleschKincaidGrade", "longSentenceShare"]);
+        assert!(r.deviations[0].message.contains("below"));
+    }
+}
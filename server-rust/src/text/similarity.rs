@@ -0,0 +1,32 @@
+// TF-IDF cosine similarity over stemmed words, for picking relevant examples without an embedding service.
+use std::collections::HashMap;
+
+use super::tokenize;
+
+fn terms(text: &str) -> HashMap<String, f32> {
+    let mut tf = HashMap::new();
+    for w in tokenize::words(&text.to_lowercase()) {
+        if w.text.chars().count() < 3 { continue; }
+        *tf.entry(tokenize::stem(w.text)).or_insert(0.0) += 1.0;
+    }
+    tf
+}
+
+/// Cosine similarity (0–1) of `query` to each document, with IDF weights taken from `docs`.
+pub fn scores(query: &str, docs: &[&str]) -> Vec<f32> {
+    let doc_terms: Vec<HashMap<String, f32>> = docs.iter().map(|d| terms(d)).collect();
+    let mut df: HashMap<&str, f32> = HashMap::new();
+    for t in &doc_terms { for k in t.keys() { *df.entry(k.as_str()).or_insert(0.0) += 1.0; } }
+    let n = docs.len() as f32;
+    let idf = |k: &str| ((n + 1.0) / (df.get(k).copied().unwrap_or(0.0) + 1.0)).ln() + 1.0;
+    let weigh = |t: &HashMap<String, f32>| -> HashMap<String, f32> { t.iter().map(|(k, v)| (k.clone(), (1.0 + v.ln()) * idf(k))).collect() };
+    let norm = |v: &HashMap<String, f32>| v.values().map(|x| x * x).sum::<f32>().sqrt();
+    let q = weigh(&terms(query));
+    let qn = norm(&q);
+    doc_terms.iter().map(|t| {
+        let d = weigh(t);
+        let dn = norm(&d);
+        if qn == 0.0 || dn == 0.0 { return 0.0; }
+        q.iter().filter_map(|(k, w)| d.get(k).map(|x| w * x)).sum::<f32>() / (qn * dn)
+    }).collect()
+}
@@ -0,0 +1,142 @@
+// Corpus-level style measurements for deriving a voice from sample texts. English-oriented
+// heuristics: contractions, pronouns and the formality proxy assume English copy.
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use super::{readability, tokenize};
+
+const FIRST_SINGULAR: &[&str] = &["i", "me", "my", "mine", "i'm", "i've", "i'll", "i'd"];
+const FIRST_PLURAL: &[&str] = &["we", "us", "our", "ours", "we're", "we've", "we'll", "we'd"];
+const SECOND: &[&str] = &["you", "your", "yours", "you're", "you've", "you'll", "you'd"];
+// Skipped when picking signature words
+const COMMON: &[&str] = &[
+    "about", "after", "also", "been", "before", "being", "could", "does", "each", "even", "every", "from", "have", "here",
+    "into", "just", "like", "made", "make", "more", "most", "much", "need", "only", "other", "over", "same", "should",
+    "some", "than", "that", "their", "them", "then", "there", "these", "they", "this", "those", "through", "very", "want",
+    "what", "when", "where", "which", "while", "will", "with", "would", "your", "yours", "you're", "we're", "it's",
+];
+const MATTR_WINDOW: usize = 100;
+const SIGNATURE_WORDS: usize = 12;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct Pronouns { pub firstSingular: f32, pub firstPlural: f32, pub second: f32 }
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct Stylometry {
+    pub samples: usize,
+    pub words: usize,
+    pub avgSentenceLength: f32,
+    pub sentenceLengthStdDev: f32,
+    pub fleschKincaidGrade: f32,
+    pub avgWordLength: f32,
+    // Per 100 words
+    pub contractions: f32,
+    pub pronouns: Pronouns,
+    // Share of sentences
+    pub exclamations: f32,
+    pub questions: f32,
+    // Moving-average type/token ratio over 100-word windows (length-independent vocabulary richness)
+    pub vocabularyRichness: f32,
+    // 0 = very casual, 100 = very formal
+    pub formality: f32,
+    pub formalityLabel: &'static str,
+    // Frequent content words shared across samples
+    pub signatureWords: Vec<String>,
+}
+
+pub fn analyze(samples: &[String]) -> Stylometry {
+    let mut lengths: Vec<f32> = Vec::new();
+    let (mut exclaim, mut question) = (0usize, 0usize);
+    let mut words: Vec<String> = Vec::new();
+    let mut doc_freq: HashMap<String, (usize, usize)> = HashMap::new();
+    for s in samples {
+        for sent in tokenize::sentences(s) {
+            let n = tokenize::words(sent.text).len();
+            if n == 0 { continue; }
+            lengths.push(n as f32);
+            let end = sent.text.trim_end();
+            if end.ends_with('!') { exclaim += 1; } else if end.ends_with('?') { question += 1; }
+        }
+        let lower: Vec<String> = tokenize::words(s).iter().map(|w| w.text.to_lowercase().replace('’', "'")).collect();
+        let mut seen = HashSet::new();
+        for w in &lower {
+            if w.chars().count() < 4 || COMMON.contains(&w.as_str()) || w.chars().any(|c| c.is_ascii_digit()) { continue; }
+            let e = doc_freq.entry(w.clone()).or_insert((0, 0));
+            e.1 += 1;
+            if seen.insert(w.clone()) { e.0 += 1; }
+        }
+        words.extend(lower);
+    }
+
+    let wc = words.len().max(1) as f32;
+    let per100 = |list: &[&str]| round1(words.iter().filter(|w| list.contains(&w.as_str())).count() as f32 * 100.0 / wc);
+    let contractions = round1(words.iter().filter(|w| w.contains('\'')).count() as f32 * 100.0 / wc);
+    let n = lengths.len().max(1) as f32;
+    let mean = lengths.iter().sum::<f32>() / n;
+    let sd = (lengths.iter().map(|l| (l - mean).powi(2)).sum::<f32>() / n).sqrt();
+    let avg_word = words.iter().map(|w| w.chars().count()).sum::<usize>() as f32 / wc;
+    let pronouns = Pronouns { firstSingular: per100(FIRST_SINGULAR), firstPlural: per100(FIRST_PLURAL), second: per100(SECOND) };
+    let exclamations = round2(exclaim as f32 / n);
+
+    // Heuristic: longer words and sentences read formal; contractions, direct address and exclamations read casual
+    let formality = (50.0 + (avg_word - 4.6) * 18.0 + (mean - 16.0) * 0.8 - contractions * 5.0
+        - (pronouns.second + pronouns.firstSingular) * 0.8 - exclamations * 40.0).clamp(0.0, 100.0);
+    let formality_label = if formality < 35.0 { "casual" } else if formality < 65.0 { "neutral" } else { "formal" };
+
+    let min_docs = if samples.len() >= 2 { 2 } else { 1 };
+    let mut signature: Vec<(String, (usize, usize))> = doc_freq.into_iter().filter(|(_, (df, _))| *df >= min_docs).collect();
+    signature.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    Stylometry {
+        samples: samples.len(),
+        words: words.len(),
+        avgSentenceLength: round1(mean),
+        sentenceLengthStdDev: round1(sd),
+        fleschKincaidGrade: readability::measure(&samples.join("\n\n"), 25).fleschKincaidGrade,
+        avgWordLength: round1(avg_word),
+        contractions,
+        pronouns,
+        exclamations,
+        questions: round2(question as f32 / n),
+        vocabularyRichness: round2(mattr(&words)),
+        formality: round1(formality),
+        formalityLabel: formality_label,
+        signatureWords: signature.into_iter().take(SIGNATURE_WORDS).map(|(w, _)| w).collect(),
+    }
+}
+
+fn mattr(words: &[String]) -> f32 {
+    if words.is_empty() { return 0.0; }
+    if words.len() <= MATTR_WINDOW { return words.iter().collect::<HashSet<_>>().len() as f32 / words.len() as f32; }
+    let count = (words.len() - MATTR_WINDOW + 1) as f32;
+    words.windows(MATTR_WINDOW).map(|w| w.iter().collect::<HashSet<_>>().len() as f32 / MATTR_WINDOW as f32).sum::<f32>() / count
+}
+
+fn round1(x: f32) -> f32 { (x * 10.0).round() / 10.0 }
+fn round2(x: f32) -> f32 { (x * 100.0).round() / 100.0 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casual_samples_score_casual() {
+        let casual: Vec<String> = vec![
+            "Hey! We're so glad you're here. You'll love the new trails.".into(),
+            "You asked, we listened. Our trails are open again and you're invited!".into(),
+        ];
+        let formal: Vec<String> = vec![
+            "The organization maintains comprehensive documentation regarding regulatory requirements and institutional obligations.".into(),
+            "Institutional stakeholders receive quarterly documentation describing regulatory developments and organizational responsibilities.".into(),
+        ];
+        let (c, f) = (analyze(&casual), analyze(&formal));
+        assert_eq!(c.formalityLabel, "casual");
+        assert_eq!(f.formalityLabel, "formal");
+        assert!(c.contractions > 10.0 && c.pronouns.second > c.pronouns.firstSingular);
+        assert!(c.signatureWords.contains(&"trails".to_string()));
+        assert!(f.signatureWords.contains(&"documentation".to_string()));
+    }
+}
@@ -0,0 +1,100 @@
+// Word/sentence splitting shared by the deterministic (model-free) text checks.
+// Offsets are byte offsets into the original text; convert with `char_offset` before returning to clients.
+
+#[derive(Debug, Clone, Copy)]
+pub struct Span<'a> { pub text: &'a str, pub start: usize, pub end: usize }
+
+fn is_word_char(c: char) -> bool { c.is_alphanumeric() || c == '\'' || c == '’' }
+
+/// Alphanumeric runs (apostrophes kept, hyphens split), so "best-in-class" and "best in class" tokenize alike.
+pub fn words(text: &str) -> Vec<Span<'_>> {
+    let mut out = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, c) in text.char_indices() {
+        match (is_word_char(c), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => { push_word(&mut out, text, s, i); start = None; }
+            _ => {}
+        }
+    }
+    if let Some(s) = start { push_word(&mut out, text, s, text.len()); }
+    out
+}
+
+fn push_word<'a>(out: &mut Vec<Span<'a>>, text: &'a str, s: usize, e: usize) {
+    // Strip leading/trailing quotes that were captured as apostrophes
+    let raw = &text[s..e];
+    let lead = raw.len() - raw.trim_start_matches(['\'', '’']).len();
+    let trimmed = raw.trim_start_matches(['\'', '’']).trim_end_matches(['\'', '’']);
+    if trimmed.is_empty() { return; }
+    let start = s + lead;
+    out.push(Span { text: trimmed, start, end: start + trimmed.len() });
+}
+
+/// Sentences end at . ! ? (followed by whitespace or end), at blank lines, and before list items/headings.
+pub fn sentences(text: &str) -> Vec<Span<'_>> {
+    let mut out = Vec::new();
+    let mut start = 0usize;
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let (pos, c) = chars[i];
+        let mut cut: Option<usize> = None;
+        if matches!(c, '.' | '!' | '?') {
+            // absorb runs like "?!" and closing quotes/brackets
+            let mut j = i + 1;
+            while j < chars.len() && matches!(chars[j].1, '.' | '!' | '?' | '"' | '\'' | '”' | '’' | ')') { j += 1; }
+            if j >= chars.len() || chars[j].1.is_whitespace() {
+                cut = Some(if j < chars.len() { chars[j].0 } else { text.len() });
+                i = j;
+            }
+        } else if c == '\n' {
+            let rest = text[pos + 1..].trim_start_matches([' ', '\t']);
+            if rest.starts_with('\n') || starts_block(rest) { cut = Some(pos); }
+        }
+        if let Some(end) = cut {
+            push_sentence(&mut out, text, start, end);
+            start = end;
+        }
+        i += 1;
+    }
+    push_sentence(&mut out, text, start, text.len());
+    out
+}
+
+fn starts_block(line: &str) -> bool {
+    if line.starts_with('#') || line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") || line.starts_with('>') { return true; }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    digits > 0 && line[digits..].starts_with(". ")
+}
+
+fn push_sentence<'a>(out: &mut Vec<Span<'a>>, text: &'a str, s: usize, e: usize) {
+    let raw = &text[s..e];
+    let lead = raw.len() - raw.trim_start().len();
+    let trimmed = raw.trim();
+    if trimmed.is_empty() || words(trimmed).is_empty() { return; }
+    let start = s + lead;
+    out.push(Span { text: trimmed, start, end: start + trimmed.len() });
+}
+
+/// Light suffix-stripping stemmer: enough to match "optimize"/"optimizing"/"optimization"
+/// and "synergy"/"synergies" without pulling in a full Porter implementation.
+pub fn stem(word: &str) -> String {
+    let w = word.to_lowercase().replace('’', "'");
+    let w = w.strip_suffix("'s").unwrap_or(&w).to_string();
+    const SUFFIXES: &[(&str, &str)] = &[
+        ("ations", ""), ("atives", ""), ("ation", ""), ("ative", ""),
+        ("ments", ""), ("ment", ""), ("ness", ""), ("ingly", ""), ("ings", ""), ("ing", ""),
+        ("edly", ""), ("ions", ""), ("ives", ""), ("ies", "y"), ("ion", ""), ("ive", ""),
+        ("ed", ""), ("es", ""), ("ly", ""), ("s", ""), ("e", ""),
+    ];
+    for (suf, rep) in SUFFIXES {
+        if *suf == "s" && w.ends_with("ss") { continue; }
+        if let Some(base) = w.strip_suffix(suf) {
+            if base.chars().count() >= 3 { return format!("{}{}", base, rep); }
+        }
+    }
+    w
+}
+
+pub fn char_offset(text: &str, byte: usize) -> usize { text[..byte.min(text.len())].chars().count() }
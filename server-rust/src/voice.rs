@@ -0,0 +1,143 @@
+// Voice derivation from sample texts: local stylometry plus a model-inferred Tone whose traits and
+// rules cite quotes from the samples. Items whose quote isn't found in any sample are reported as
+// unsupported evidence and left out of the Tone, so they never reach the guide.
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::adapters::AdapterDyn;
+use crate::models::{BrandGuide, DosAndDonts, Tone};
+use crate::text::stylometry::{self, Stylometry};
+
+pub const MIN_SAMPLES: usize = 5;
+pub const MAX_SAMPLES: usize = 50;
+// Per-sample and total prompt budgets
+const SAMPLE_CHARS: usize = 3000;
+const TOTAL_CHARS: usize = 40_000;
+
+fn schema() -> Value {
+    let cited = |key: &str| json!({"type": "array", "items": {"type": "object", "additionalProperties": false, "required": [key, "quote"], "properties": {key: {"type": "string"}, "quote": {"type": "string"}}}});
+    json!({
+        "type": "object", "additionalProperties": false, "required": ["traits", "description", "dos", "donts"],
+        "properties": {"traits": cited("trait"), "description": {"type": "string"}, "dos": cited("rule"), "donts": cited("rule")}
+    })
+}
+
+fn build_prompt(samples: &[String], style: &Stylometry, guide: Option<&BrandGuide>) -> String {
+    let mut budget = TOTAL_CHARS;
+    let mut corpus = String::new();
+    for (i, s) in samples.iter().enumerate() {
+        let take: String = s.chars().take(SAMPLE_CHARS.min(budget)).collect();
+        if take.is_empty() { break; }
+        budget -= take.chars().count();
+        corpus.push_str(&format!("[Sample {}]\n{}\n\n", i + 1, take.trim()));
+    }
+    let brand = guide.map(|g| format!("Brand: {} ({}). Audience: {}\n", g.brandName, g.industry, g.audience)).unwrap_or_default();
+    format!(
+        "Infer the brand voice shared by these approved sample texts.\n{}\
+Measured style: {} words; average sentence {} words (sd {}); grade {}; {} contractions and {} second-person pronouns per 100 words; formality {} ({}/100); signature words: {}.\n\
+Return 3-5 tone traits, a 2-3 sentence description, 4-6 dos and 4-6 don'ts.\n\
+Every trait and do must cite a short verbatim quote (under 20 words) copied exactly from the samples that shows it. \
+For don'ts, quote the sample wording that shows the preferred alternative.\n\n{}",
+        brand, style.words, style.avgSentenceLength, style.sentenceLengthStdDev, style.fleschKincaidGrade,
+        style.contractions, style.pronouns.second, style.formalityLabel, style.formality, style.signatureWords.join(", "), corpus
+    )
+}
+
+/// Stylometry, the derived Tone and the quotes backing each trait and rule.
+pub async fn derive(adapter: &AdapterDyn, samples: &[String], guide: Option<&BrandGuide>) -> Result<(Tone, Value)> {
+    let style = stylometry::analyze(samples);
+    let data = adapter.generate_json(&build_prompt(samples, &style, guide), Some(schema()), Some(0.3)).await?;
+    let (tone, evidence) = backed_tone(&data, samples);
+    let supported = evidence.iter().filter(|e| e["supported"] == json!(true)).count();
+    Ok((tone, json!({"stylometry": style, "evidence": evidence, "quotedShare": if evidence.is_empty() { 0.0 } else { supported as f32 / evidence.len() as f32 }})))
+}
+
+// Tone from the model output with only quote-backed traits and rules; the evidence lists every item
+fn backed_tone(data: &Value, samples: &[String]) -> (Tone, Vec<Value>) {
+    let mut evidence = Vec::new();
+    let mut collect = |kind: &str, key: &str| -> Vec<String> {
+        let mut out = Vec::new();
+        for item in data[kind].as_array().into_iter().flatten() {
+            let text = item[key].as_str().unwrap_or("").trim();
+            if text.is_empty() || evidence.iter().any(|e: &Value| e["kind"] == kind && e["text"].as_str().is_some_and(|t| t.eq_ignore_ascii_case(text))) { continue; }
+            let quote = item["quote"].as_str().unwrap_or("").trim().trim_matches(['"', '“', '”']);
+            let sample = find_quote(samples, quote);
+            evidence.push(json!({"kind": kind, "text": text, "quote": sample.map(|_| quote), "sample": sample, "supported": sample.is_some()}));
+            if sample.is_some() { out.push(text.to_string()); }
+        }
+        out
+    };
+    let traits = collect("traits", "trait");
+    let dos = collect("dos", "rule");
+    let donts = collect("donts", "rule");
+    let tone = Tone { traits, description: data["description"].as_str().unwrap_or("").trim().to_string(), dosAndDonts: DosAndDonts { dos, donts } };
+    (tone, evidence)
+}
+
+// 1-based sample index whose text contains the quote (case and whitespace insensitive)
+fn find_quote(samples: &[String], quote: &str) -> Option<usize> {
+    let norm = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase().replace('’', "'");
+    let q = norm(quote);
+    if q.split(' ').count() < 2 { return None; }
+    samples.iter().position(|s| norm(s).contains(&q)).map(|i| i + 1)
+}
+
+/// Derived traits and rules first, then the guide's existing ones that aren't duplicates; the derived description wins.
+pub fn merge_tone(guide: &mut BrandGuide, derived: &Tone) {
+    let union = |a: &[String], b: &[String]| {
+        let mut out: Vec<String> = a.to_vec();
+        for s in b { if !out.iter().any(|o| o.eq_ignore_ascii_case(s)) { out.push(s.clone()); } }
+        out
+    };
+    let t = &mut guide.tone;
+    t.traits = union(&derived.traits, &t.traits);
+    if !derived.description.is_empty() { t.description = derived.description.clone(); }
+    t.dosAndDonts.dos = union(&derived.dosAndDonts.dos, &t.dosAndDonts.dos);
+    t.dosAndDonts.donts = union(&derived.dosAndDonts.donts, &t.dosAndDonts.donts);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> Vec<String> {
+        vec!["We fix it fast.\nNo   jargon, just answers.".into(), "You\u{2019}ll hear back within a day.".into()]
+    }
+
+    #[test]
+    fn finds_quotes_across_whitespace_case_and_apostrophes() {
+        let s = samples();
+        assert_eq!(find_quote(&s, "no jargon, JUST answers"), Some(1));
+        assert_eq!(find_quote(&s, "you'll hear back"), Some(2));
+        // Single words are too weak to count as evidence
+        assert_eq!(find_quote(&s, "jargon"), None);
+        assert_eq!(find_quote(&s, "we are synergistic"), None);
+    }
+
+    #[test]
+    fn only_quoted_items_reach_the_guide() {
+        let data = json!({
+            "traits": [{"trait": "Direct", "quote": "\u{201c}No jargon, just answers\u{201d}"}, {"trait": "Luxurious", "quote": "indulge yourself tonight"}],
+            "description": "Plain and quick.",
+            "dos": [{"rule": "Promise a response time", "quote": "hear back within a day"}],
+            "donts": [{"rule": "Use jargon", "quote": ""}],
+        });
+        let (tone, evidence) = backed_tone(&data, &samples());
+        assert_eq!(tone.traits, vec!["Direct"]);
+        assert_eq!(tone.dosAndDonts.dos, vec!["Promise a response time"]);
+        assert!(tone.dosAndDonts.donts.is_empty());
+        assert_eq!(evidence.len(), 4);
+        let luxurious = evidence.iter().find(|e| e["text"] == "Luxurious").unwrap();
+        assert_eq!((luxurious["supported"].clone(), luxurious["quote"].clone()), (json!(false), json!(null)));
+
+        let mut guide: BrandGuide = serde_json::from_value(json!({
+            "brandName": "Acme", "industry": "Support", "mission": "m", "audience": "a", "taglines": [], "elevatorPitch": "p", "palette": {},
+            "tone": {"traits": ["direct", "Warm"], "description": "Old.", "dosAndDonts": {"dos": ["Be brief"], "donts": ["Shout"]}},
+        })).unwrap();
+        merge_tone(&mut guide, &tone);
+        assert_eq!(guide.tone.traits, vec!["Direct", "Warm"]);
+        assert_eq!(guide.tone.description, "Plain and quick.");
+        assert_eq!(guide.tone.dosAndDonts.dos, vec!["Promise a response time", "Be brief"]);
+        assert_eq!(guide.tone.dosAndDonts.donts, vec!["Shout"]);
+    }
+}